@@ -1,12 +1,43 @@
+use crate::{error::ParseError, solution::Solution};
+
+const DAY: u8 = 0;
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<i64>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
+
+#[aoc_generator(day)]
+pub fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    todo!()
+}
+
 #[aoc(day, part1)]
-pub fn run_part1(input: &str) -> i64 {
+pub fn run_part1(input: &[i64]) -> i64 {
     todo!()
 }
 
-/* #[aoc(day, part2)]
-pub fn run_part2(input: &str) -> i64 {
+#[aoc(day, part2)]
+pub fn run_part2(input: &[i64]) -> i64 {
     todo!()
-} */
+}
 
 #[cfg(test)]
 mod tests {
@@ -16,11 +47,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 0);
+        assert_eq!(run_part1(&parse_input(TEST_INPUT).unwrap()), 0);
     }
 
     /* #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 0);
+        assert_eq!(run_part2(&parse_input(TEST_INPUT).unwrap()), 0);
     } */
 }
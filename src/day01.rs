@@ -1,42 +1,335 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    convert::Infallible,
+    fmt::{self, Display},
+    io::BufRead,
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    error::{ParseError, ReadError},
+    parse,
+    solution::Solution,
+};
+
+const DAY: u8 = 1;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Calorie Counting");
+
+    type Input = Vec<i64>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_elves(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+#[aoc_generator(day1)]
+pub fn parse_elves(input: &str) -> Result<Vec<i64>, ParseError> {
+    elf_totals(input).collect()
+}
+
 #[aoc(day1, part1)]
-pub fn part1(input: &str) -> i32 {
-    input
-        .split("\n\n")
-        .map(|elf| {
-            elf.as_bytes()
-                .split(|b| *b == b'\n')
-                .map(parse_ascii_number)
-                .sum::<i32>()
-        })
-        .max()
-        .unwrap()
+pub fn part1(elves: &[i64]) -> i64 {
+    top_n_elves(elves, 1).iter().map(|&(_, total)| total).sum()
 }
 
 #[aoc(day1, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut elves = input
-        .split("\n\n")
-        .map(|elf| {
-            elf.as_bytes()
-                .split(|b| *b == b'\n')
-                .map(parse_ascii_number)
-                .sum::<i32>()
-        })
-        .collect::<Vec<_>>();
+pub fn part2(elves: &[i64]) -> i64 {
+    top_n_elves(elves, 3).iter().map(|&(_, total)| total).sum()
+}
+
+/// The index and total of the `n` elves carrying the most calories, most first.
+///
+/// Elves with equal totals are ranked by index, so the earlier one wins a place at the cutoff.
+/// Fewer than `n` elves are returned if there aren't that many.
+pub fn top_n_elves(elves: &[i64], n: usize) -> Vec<(usize, i64)> {
+    let Ok(top) = top_n(elves.iter().map(|&total| Ok::<_, Infallible>(total)), n);
+    top
+}
+
+/// [`top_n_elves`] over totals [streamed](ElfTotals) from `reader`, holding only `n` at a time.
+pub fn read_top_n_elves(reader: impl BufRead, n: usize) -> Result<Vec<(usize, i64)>, ReadError> {
+    top_n(ElfTotals::new(reader), n)
+}
+
+fn top_n<E>(
+    totals: impl IntoIterator<Item = Result<i64, E>>,
+    n: usize,
+) -> Result<Vec<(usize, i64)>, E> {
+    // A min-heap of the best so far, ordered so the first one to drop out is on top. It grows as
+    // needed, since `n` can be far more than the number of elves.
+    let mut top = BinaryHeap::new();
 
-    elves.sort_unstable_by(|a, b| a.cmp(b).reverse());
-    elves[0..3].iter().sum()
+    for (index, total) in totals.into_iter().enumerate() {
+        top.push(Reverse((total?, Reverse(index))));
+
+        if top.len() > n {
+            top.pop();
+        }
+    }
+
+    Ok(top
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((total, Reverse(index)))| (index, total))
+        .collect())
+}
+
+/// Each elf's total calories, read a line at a time from a [`BufRead`] such as stdin.
+///
+/// Unlike [`elf_totals`] this never holds more than one line of input, so it works on inputs of
+/// any size. Line endings may be LF or CRLF, and a trailing blank line is fine.
+pub struct ElfTotals<R> {
+    reader: R,
+    line: Vec<u8>,
+    line_idx: usize,
+    done: bool,
+}
+
+impl<R: BufRead> ElfTotals<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+            line_idx: 0,
+            done: false,
+        }
+    }
 }
 
-pub fn parse_ascii_number(slice: &[u8]) -> i32 {
-    slice
+impl<R: BufRead> Iterator for ElfTotals<R> {
+    type Item = Result<i64, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut total = None;
+
+        while !self.done {
+            self.line.clear();
+
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    let line_idx = self.line_idx;
+                    self.line_idx += 1;
+
+                    let line = self.line.strip_suffix(b"\n").unwrap_or(&self.line);
+                    let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+                    // A blank line ends an elf, but an elf can't be empty.
+                    if line.is_empty() && total.is_some() {
+                        return total.map(Ok);
+                    }
+
+                    match parse_ascii_number(line) {
+                        Ok(calories) => total = Some(total.unwrap_or(0) + i64::from(calories)),
+                        Err(error) => {
+                            self.done = true;
+                            return Some(Err(error.on_line(line_idx).into()));
+                        }
+                    }
+                }
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error.into()));
+                }
+            }
+        }
+
+        total.map(Ok)
+    }
+}
+
+/// The total calories carried by each elf, in input order.
+pub fn elf_totals(input: &str) -> impl Iterator<Item = Result<i64, ParseError>> + '_ {
+    elf_loads(input).map(|elf| elf.map(|(total, _)| total))
+}
+
+/// The total calories and number of items carried by each elf, in input order.
+fn elf_loads(input: &str) -> impl Iterator<Item = Result<(i64, usize), ParseError>> + '_ {
+    let mut line_idx = 0;
+
+    input.split("\n\n").map(move |elf| {
+        let first_line = line_idx;
+        line_idx += elf.bytes().filter(|&b| b == b'\n').count() + 2;
+
+        elf.as_bytes().split(|b| *b == b'\n').enumerate().try_fold(
+            (0, 0),
+            |(total, items), (i, item)| {
+                let calories = parse_ascii_number(item).map_err(|e| e.on_line(first_line + i))?;
+                Ok((total + i64::from(calories), items + 1))
+            },
+        )
+    })
+}
+
+pub fn parse_ascii_number(slice: &[u8]) -> Result<i32, ParseError> {
+    parse::exact(slice).map_err(|e| e.into_parse_error(DAY, slice))
+}
+
+/// The percentiles of the totals that [`CalorieStats`] reports.
+pub const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+const HISTOGRAM_BUCKETS: i64 = 10;
+
+/// Widest bar drawn in the text histogram.
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// How the calories are spread over the elves, of which there is at least one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalorieStats {
+    /// Each elf's total calories, in input order.
+    pub totals: Vec<i64>,
+    /// How many items each elf carries, in input order.
+    pub items: Vec<usize>,
+    pub mean: f64,
+    pub median: f64,
+    /// Nearest-rank [`PERCENTILES`] of the totals, as `(percentile, total)`.
+    pub percentiles: Vec<(u8, i64)>,
+    /// Equally wide ranges of totals from the smallest to the largest.
+    pub histogram: Vec<Bucket>,
+}
+
+/// The number of elves whose totals are in `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: i64,
+    pub end: i64,
+    pub elves: usize,
+}
+
+/// Summarises the calories carried by each elf in `input`.
+pub fn calorie_stats(input: &str) -> Result<CalorieStats, ParseError> {
+    let (totals, items): (Vec<_>, _) = elf_loads(input)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .unzip();
+
+    let mut sorted = totals.clone();
+    sorted.sort_unstable();
+
+    let count = sorted.len();
+    let mean = sorted.iter().map(|&t| t as f64).sum::<f64>() / count as f64;
+    let median = (sorted[(count - 1) / 2] as f64 + sorted[count / 2] as f64) / 2.0;
+
+    let percentiles = PERCENTILES
         .iter()
-        .fold(0i32, |num, &byte| (num * 10) + i32::from(byte - b'0'))
+        .map(|&p| {
+            let rank = (p as usize * count).div_ceil(100);
+            (p, sorted[rank.clamp(1, count) - 1])
+        })
+        .collect();
+
+    let (min, max) = (sorted[0], sorted[count - 1]);
+    let width = (max - min + HISTOGRAM_BUCKETS) / HISTOGRAM_BUCKETS;
+
+    let histogram = (min..=max)
+        .step_by(width as usize)
+        .map(|start| {
+            let end = (start + width - 1).min(max);
+            let elves = sorted.iter().filter(|t| (start..=end).contains(t)).count();
+
+            Bucket { start, end, elves }
+        })
+        .collect();
+
+    Ok(CalorieStats {
+        totals,
+        items,
+        mean,
+        median,
+        percentiles,
+        histogram,
+    })
+}
+
+impl CalorieStats {
+    pub fn to_json(&self) -> Value {
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|b| json!({ "start": b.start, "end": b.end, "elves": b.elves }))
+            .collect::<Vec<_>>();
+
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|&(p, total)| (format!("p{p}"), json!(total)))
+            .collect::<serde_json::Map<_, _>>();
+
+        json!({
+            "elves": self.totals.len(),
+            "totals": self.totals,
+            "items": self.items,
+            "min": self.totals.iter().min(),
+            "max": self.totals.iter().max(),
+            "mean": self.mean,
+            "median": self.median,
+            "percentiles": percentiles,
+            "histogram": histogram,
+        })
+    }
+}
+
+impl Display for CalorieStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let elves = self.totals.len();
+        let items = self.items.iter().sum::<usize>();
+        let min = self.totals.iter().min().unwrap_or(&0);
+        let max = self.totals.iter().max().unwrap_or(&0);
+
+        writeln!(f, "elves   {elves}")?;
+        writeln!(
+            f,
+            "items   {items} ({:.1} per elf)",
+            items as f64 / elves as f64
+        )?;
+        writeln!(f, "min     {min}")?;
+        writeln!(f, "max     {max}")?;
+        writeln!(f, "mean    {:.1}", self.mean)?;
+        writeln!(f, "median  {}", self.median)?;
+
+        for (p, total) in &self.percentiles {
+            writeln!(f, "{:8}{total}", format!("p{p}"))?;
+        }
+
+        let labels = self
+            .histogram
+            .iter()
+            .map(|b| format!("{}-{}", b.start, b.end))
+            .collect::<Vec<_>>();
+        let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+
+        writeln!(f, "histogram")?;
+
+        for (label, bucket) in labels.iter().zip(&self.histogram) {
+            let bar = "#".repeat((bucket.elves * HISTOGRAM_WIDTH).div_ceil(most.max(1)));
+            writeln!(f, "  {label:>label_width$} {:>4} {bar}", bucket.elves)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::*;
 
     static TEST_INPUT: &str = "1000
 2000
@@ -55,11 +348,151 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(TEST_INPUT), 24000);
+        assert_eq!(part1(&parse_elves(TEST_INPUT).unwrap()), 24000);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(TEST_INPUT), 45000);
+        assert_eq!(part2(&parse_elves(TEST_INPUT).unwrap()), 45000);
+    }
+
+    #[test]
+    fn top_elves_example() {
+        let elves = parse_elves(TEST_INPUT).unwrap();
+
+        assert_eq!(top_n_elves(&elves, 3), [(3, 24000), (2, 11000), (4, 10000)]);
+    }
+
+    #[test]
+    fn top_elves_ties() {
+        let elves = [5, 9, 7, 9, 5, 7];
+
+        assert_eq!(top_n_elves(&elves, 1), [(1, 9)]);
+        assert_eq!(top_n_elves(&elves, 3), [(1, 9), (3, 9), (2, 7)]);
+        assert_eq!(top_n_elves(&elves, 5)[4], (0, 5));
+        assert_eq!(top_n_elves(&elves, 0), []);
+    }
+
+    #[test]
+    fn top_elves_beyond_count() {
+        assert_eq!(top_n_elves(&[3, 8], 5), [(1, 8), (0, 3)]);
+        assert_eq!(top_n_elves(&[], 3), []);
+        assert_eq!(top_n_elves(&[3, 8], usize::MAX), [(1, 8), (0, 3)]);
+        assert_eq!(part2(&[3, 8]), 11);
+        assert_eq!(part1(&[]), 0);
+    }
+
+    #[test]
+    fn streams_totals() {
+        let totals = ElfTotals::new(TEST_INPUT.as_bytes()).collect::<Result<Vec<_>, _>>();
+        assert_eq!(totals.unwrap(), parse_elves(TEST_INPUT).unwrap());
+
+        let crlf = "1\r\n2\r\n\r\n4\r\n\r\n";
+        let totals = ElfTotals::new(crlf.as_bytes()).collect::<Result<Vec<_>, _>>();
+        assert_eq!(totals.unwrap(), [3, 4]);
+
+        let top = read_top_n_elves(TEST_INPUT.as_bytes(), 2).unwrap();
+        assert_eq!(top, [(3, 24000), (2, 11000)]);
+    }
+
+    #[test]
+    fn totals_beyond_32_bits() {
+        let input = "2147483647\n1\n\n2147483647\n2147483647";
+
+        assert_eq!(parse_elves(input).unwrap(), [2147483648, 4294967294]);
+        assert_eq!(part2(&parse_elves(input).unwrap()), 6442450942);
+
+        let top = read_top_n_elves(input.as_bytes(), 1).unwrap();
+        assert_eq!(top, [(1, 4294967294)]);
+    }
+
+    #[test]
+    fn malformed_streamed_calories() {
+        let mut totals = ElfTotals::new("1000\n\n2000\n30x0\n".as_bytes());
+        assert_eq!(totals.next().unwrap().unwrap(), 1000);
+
+        let Some(Err(ReadError::Parse(error))) = totals.next() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column, error.found), (4, 3, Some('x')));
+        assert!(totals.next().is_none());
+
+        let Err(ReadError::Parse(error)) = read_top_n_elves("1\n\n\n2".as_bytes(), 1) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column, error.found), (3, 1, None));
+    }
+
+    #[test]
+    fn stats_example() {
+        let stats = calorie_stats(TEST_INPUT).unwrap();
+
+        assert_eq!(stats.totals, [6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(stats.items, [3, 1, 2, 3, 1]);
+        assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+        assert_eq!(
+            stats.percentiles,
+            [
+                (10, 4000),
+                (25, 6000),
+                (50, 10000),
+                (75, 11000),
+                (90, 24000)
+            ]
+        );
+
+        assert_eq!(stats.histogram.len(), 10);
+        assert_eq!(
+            stats.histogram[0],
+            Bucket {
+                start: 4000,
+                end: 6000,
+                elves: 2
+            }
+        );
+        assert_eq!(stats.histogram[9].end, 24000);
+        assert_eq!(stats.histogram.iter().map(|b| b.elves).sum::<usize>(), 5);
+
+        let json = stats.to_json();
+        assert_eq!(json["max"], 24000);
+        assert_eq!(json["percentiles"]["p75"], 11000);
+        assert_eq!(json["histogram"][9]["elves"], 1);
+        assert!(stats.to_string().contains("median  10000\n"));
+    }
+
+    #[test]
+    fn stats_of_even_and_equal_totals() {
+        let stats = calorie_stats("1\n\n2\n\n3\n\n4").unwrap();
+        assert_eq!((stats.mean, stats.median), (2.5, 2.5));
+        assert_eq!(stats.histogram.len(), 4);
+
+        let stats = calorie_stats("7\n\n7").unwrap();
+        assert_eq!(
+            stats.histogram,
+            [Bucket {
+                start: 7,
+                end: 7,
+                elves: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn stats_items_match_totals() {
+        let stats = calorie_stats("1\n2\n3\n\n4\n\n5\n6").unwrap();
+        assert_eq!((stats.totals, stats.items), (vec![6, 4, 11], vec![3, 1, 2]));
+
+        let error = calorie_stats("1\n\n\n2").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (3, 1, None));
+
+        let error = calorie_stats("1\r\n2").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (1, 2, Some('\r')));
+    }
+
+    #[test]
+    fn malformed_calories() {
+        let error = parse_elves("1000\n\n2000\n30x0").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (4, 3, Some('x')));
     }
 }
@@ -1,110 +1,511 @@
-use std::cmp::Ordering;
+use std::fmt::{self, Display};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-#[repr(i32)]
-pub enum RpsChoice {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+use itertools::Itertools;
+use serde_json::{json, Map, Value};
+
+use crate::{error::ParseError, solution::Solution};
+
+const DAY: u8 = 2;
+
+/// A game where each of an odd number of options beats exactly half of the others, like rock paper
+/// scissors and its bigger variants.
+///
+/// Options are indices into the game. Which options beat which, and what each option and outcome
+/// is worth, are all data, so the same rules score any such game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CyclicGame {
+    names: Vec<String>,
+    /// `beats[a][b]` is whether option `a` beats option `b`.
+    beats: Vec<Vec<bool>>,
+    option_scores: Vec<i32>,
+    /// The score for a loss, a draw and a win, in that order.
+    outcome_scores: [i32; 3],
 }
 
-impl PartialOrd for RpsChoice {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        use RpsChoice::*;
+impl CyclicGame {
+    /// A game of the `names` options, where `(a, b)` in `wins` means option `a` beats option `b`.
+    ///
+    /// Each option scores one more than the one before it, starting from 1, and outcomes score 0,
+    /// 3 and 6 as in the puzzle; [`with_scores`](Self::with_scores) changes that. Fails unless the
+    /// number of options is odd and every option beats exactly half of the others.
+    pub fn new(names: &[&str], wins: &[(usize, usize)]) -> Result<Self, String> {
+        let len = names.len();
+
+        if len.is_multiple_of(2) {
+            return Err(format!(
+                "a cyclic game needs an odd number of options, not {len}"
+            ));
+        }
+
+        let mut beats = vec![vec![false; len]; len];
+
+        for &(a, b) in wins {
+            if a >= len || b >= len {
+                return Err(format!("no option {} in a game of {len}", a.max(b)));
+            }
+
+            if a == b || beats[a][b] || beats[b][a] {
+                return Err(format!(
+                    "{} and {} can only be beaten one way",
+                    names[a], names[b]
+                ));
+            }
+
+            beats[a][b] = true;
+        }
 
-        Some(match (*self, *other) {
-            (Paper, Rock) | (Rock, Scissors) | (Scissors, Paper) => Ordering::Greater,
-            (Rock, Paper) | (Scissors, Rock) | (Paper, Scissors) => Ordering::Less,
-            _ => Ordering::Equal,
+        if let Some(a) = (0..len).find(|&a| beats[a].iter().filter(|&&b| b).count() != len / 2) {
+            return Err(format!(
+                "{} should beat exactly {} options",
+                names[a],
+                len / 2
+            ));
+        }
+
+        Ok(Self {
+            names: names.iter().map(|&name| name.to_owned()).collect(),
+            beats,
+            option_scores: (1..).take(len).collect(),
+            outcome_scores: [0, 3, 6],
         })
     }
-}
 
-impl Ord for RpsChoice {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+    /// A game where each option beats the options an odd number of places before it, wrapping
+    /// around.
+    pub fn cyclic(names: &[&str]) -> Result<Self, String> {
+        let len = names.len();
+        let wins = (0..len)
+            .flat_map(|a| (0..len).map(move |b| (a, b)))
+            .filter(|&(a, b)| (a + len - b) % len % 2 == 1)
+            .collect::<Vec<_>>();
+
+        Self::new(names, &wins)
+    }
+
+    /// Scores each option by `option_scores` and each loss, draw and win by `outcome_scores`.
+    pub fn with_scores(
+        mut self,
+        option_scores: &[i32],
+        outcome_scores: [i32; 3],
+    ) -> Result<Self, String> {
+        if option_scores.len() != self.len() {
+            return Err(format!(
+                "expected {} option scores, got {}",
+                self.len(),
+                option_scores.len()
+            ));
+        }
+
+        self.option_scores = option_scores.to_vec();
+        self.outcome_scores = outcome_scores;
+        Ok(self)
+    }
+
+    /// Rock, paper and scissors, as the puzzle plays it.
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors"]).expect("odd number of options")
     }
+
+    /// Rock paper scissors with lizard and Spock added.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors", "Spock", "Lizard"])
+            .expect("odd number of options")
+    }
+
+    /// The number of options.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, option: usize) -> &str {
+        &self.names[option]
+    }
+
+    /// How a round ends for the player.
+    pub fn outcome(&self, player: usize, opponent: usize) -> Outcome {
+        if self.beats[player][opponent] {
+            Outcome::Win
+        } else if self.beats[opponent][player] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The highest-scoring option that gets `outcome` against `opponent`.
+    pub fn choice_for(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.len())
+            .filter(|&player| self.outcome(player, opponent) == outcome)
+            .max_by_key(|&player| self.option_scores[player])
+            .expect("every outcome is possible against every option")
+    }
+
+    /// The player's score for a round, for their option plus the outcome.
+    pub fn score(&self, opponent: usize, player: usize) -> i32 {
+        self.option_scores[player] + self.outcome_scores[self.outcome(player, opponent) as usize]
+    }
+}
+
+/// Which letters stand for what in a strategy guide: options of a game for the opponent, and `R`
+/// for the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Letters<R> {
+    opponent: Vec<u8>,
+    response: Vec<(u8, R)>,
 }
 
-impl From<u8> for RpsChoice {
-    fn from(byte: u8) -> Self {
-        match byte {
-            b'A' | b'X' => Self::Rock,
-            b'B' | b'Y' => Self::Paper,
-            b'C' | b'Z' => Self::Scissors,
-            _ => unreachable!(),
+impl<R: Copy> Letters<R> {
+    /// The `n`th opponent letter stands for option `n` of `game`, and the `n`th response letter
+    /// for `meanings[n]`.
+    fn new(
+        game: &CyclicGame,
+        opponent: &str,
+        response: &str,
+        meanings: &[R],
+    ) -> Result<Self, String> {
+        if opponent.len() != game.len() {
+            return Err(format!(
+                "expected {} opponent letters, got `{opponent}`",
+                game.len()
+            ));
+        }
+
+        if response.len() != meanings.len() {
+            return Err(format!(
+                "expected {} response letters, got `{response}`",
+                meanings.len()
+            ));
         }
+
+        for letters in [opponent, response] {
+            let bytes = letters.as_bytes();
+
+            if let Some(i) = (0..bytes.len()).find(|&i| bytes[..i].contains(&bytes[i])) {
+                return Err(format!("letter `{}` is used twice", bytes[i] as char));
+            }
+
+            if !letters.bytes().all(|b| b.is_ascii_graphic()) {
+                return Err(format!("`{letters}` should be letters"));
+            }
+        }
+
+        Ok(Self {
+            opponent: opponent.bytes().collect(),
+            response: response.bytes().zip(meanings.iter().copied()).collect(),
+        })
+    }
+
+    fn opponent(&self, byte: u8) -> Result<usize, ParseError> {
+        self.opponent
+            .iter()
+            .position(|&b| b == byte)
+            .ok_or_else(|| ParseError::unexpected(DAY, &[byte], 0, "an opponent letter"))
+    }
+
+    fn response(&self, byte: u8) -> Result<R, ParseError> {
+        self.response
+            .iter()
+            .find(|&&(b, _)| b == byte)
+            .map(|&(_, meaning)| meaning)
+            .ok_or_else(|| ParseError::unexpected(DAY, &[byte], 0, "a response letter"))
+    }
+}
+
+impl Letters<usize> {
+    /// Letters for reading each response as the option of `game` to play.
+    pub fn choices(game: &CyclicGame, opponent: &str, response: &str) -> Result<Self, String> {
+        let options = (0..game.len()).collect::<Vec<_>>();
+        Self::new(game, opponent, response, &options)
     }
 }
 
-#[derive(Clone, Copy)]
+impl Letters<Outcome> {
+    /// Letters for reading each response as the outcome to aim for, a loss, draw or win.
+    pub fn outcomes(game: &CyclicGame, opponent: &str, response: &str) -> Result<Self, String> {
+        Self::new(game, opponent, response, &Outcome::ALL)
+    }
+}
+
+/// Parses a strategy guide with any `letters`, as the opponent's option and what the response
+/// stands for.
+pub fn parse_rounds<R: Copy>(
+    input: &[u8],
+    letters: &Letters<R>,
+) -> Result<Vec<(usize, R)>, ParseError> {
+    let round = |line: &[u8]| match line {
+        [left, b' ', right] => Ok((
+            letters.opponent(*left)?,
+            letters.response(*right).map_err(|e| e.shifted(2))?,
+        )),
+        [_, b' ', _, ..] => Err(ParseError::unexpected(DAY, line, 3, "end of line")),
+        [] => Err(ParseError::unexpected(DAY, line, 0, "an opponent letter")),
+        [_, b' '] => Err(ParseError::unexpected(DAY, line, 2, "a response letter")),
+        _ => Err(ParseError::unexpected(DAY, line, 1, "' '")),
+    };
+
+    input
+        .split(|&b| b == b'\n')
+        .enumerate()
+        .map(|(i, line)| round(line).map_err(|e| e.on_line(i)))
+        .collect()
+}
+
+/// How a round of `game` ends for the player, and what they score for it.
+pub fn evaluate_round(game: &CyclicGame, opponent: usize, player: usize) -> (Outcome, i32) {
+    (game.outcome(player, opponent), game.score(opponent, player))
+}
+
+/// The total score of `rounds`, each the opponent's option and the option to play.
+pub fn score_as_choices(game: &CyclicGame, rounds: &[(usize, usize)]) -> i32 {
+    rounds
+        .iter()
+        .map(|&(opponent, player)| evaluate_round(game, opponent, player).1)
+        .sum()
+}
+
+/// The total score of `rounds`, each the opponent's option and the outcome to aim for.
+pub fn score_as_outcomes(game: &CyclicGame, rounds: &[(usize, Outcome)]) -> i32 {
+    rounds
+        .iter()
+        .map(|&(opponent, outcome)| game.score(opponent, game.choice_for(opponent, outcome)))
+        .sum()
+}
+
+/// How a round ends, for the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
-pub enum DesiredOutcome {
+pub enum Outcome {
     Loss,
     Draw,
     Win,
 }
 
-impl From<u8> for DesiredOutcome {
-    fn from(byte: u8) -> Self {
-        match byte {
-            b'X' => Self::Loss,
-            b'Y' => Self::Draw,
-            b'Z' => Self::Win,
-            _ => unreachable!(),
-        }
+impl Outcome {
+    pub const ALL: [Self; 3] = [Self::Loss, Self::Draw, Self::Win];
+}
+
+const OPPONENT_LETTERS: &str = "ABC";
+const RESPONSE_LETTERS: &str = "XYZ";
+
+/// The strategy guide, read both of the ways the puzzle reads its second column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guide {
+    /// Each round as the opponent's option and the option to play.
+    pub choices: Vec<(usize, usize)>,
+    /// Each round as the opponent's option and the outcome to aim for.
+    pub outcomes: Vec<(usize, Outcome)>,
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Rock Paper Scissors");
+
+    type Input = Guide;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_guide(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        part2(input)
     }
 }
 
+/// The puzzle's letters, A to C and X to Z, for reading the response as an option of `game`, which
+/// must have three.
+pub fn puzzle_letters(game: &CyclicGame) -> Letters<usize> {
+    Letters::choices(game, OPPONENT_LETTERS, RESPONSE_LETTERS).expect("a letter per option")
+}
+
+#[aoc_generator(day2)]
+pub fn parse_guide(input: &[u8]) -> Result<Guide, ParseError> {
+    let choices = parse_rounds(input, &puzzle_letters(&CyclicGame::rock_paper_scissors()))?;
+
+    // X, Y and Z stand for the same positions among the outcomes as among the options.
+    let outcomes = choices
+        .iter()
+        .map(|&(opponent, response)| (opponent, Outcome::ALL[response]))
+        .collect();
+
+    Ok(Guide { choices, outcomes })
+}
+
 #[aoc(day2, part1)]
-pub fn part1(input: &[u8]) -> i32 {
-    input
-        .split(|&b| b == b'\n')
-        .map(|round| evaluate_round(round[0].into(), round[2].into()))
-        .sum()
+pub fn part1(guide: &Guide) -> i32 {
+    score_as_choices(&CyclicGame::rock_paper_scissors(), &guide.choices)
 }
 
 #[aoc(day2, part2)]
-pub fn part2(input: &[u8]) -> i32 {
-    input
-        .split(|&b| b == b'\n')
-        .map(|round| {
-            let opponent_choice = round[0].into();
-            let desired_player_choice =
-                get_best_choice_for_outcome(opponent_choice, round[2].into());
-            evaluate_round(opponent_choice, desired_player_choice)
-        })
-        .sum()
+pub fn part2(guide: &Guide) -> i32 {
+    score_as_outcomes(&CyclicGame::rock_paper_scissors(), &guide.outcomes)
+}
+
+/// How the guide plays out when its response letters stand for `mapping`'s options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingResult {
+    /// The option played for each response letter, in the order of the letters.
+    pub mapping: Vec<usize>,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: i32,
+}
+
+/// The guide played with every way of reading its second column as options, as part 1 reads it
+/// with just one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuideAnalysis {
+    game: CyclicGame,
+    /// The response letters, in the order mappings give their options.
+    letters: Vec<char>,
+    /// Every mapping of the letters to distinct options, ordered by the options they give.
+    pub mappings: Vec<MappingResult>,
 }
 
-pub fn get_best_choice_for_outcome(
-    opponent_choice: RpsChoice,
-    desired_outcome: DesiredOutcome,
-) -> RpsChoice {
-    use DesiredOutcome::*;
-    use RpsChoice::*;
+impl GuideAnalysis {
+    /// The highest-scoring mapping, the first of them on a tie.
+    pub fn best(&self) -> &MappingResult {
+        self.mappings
+            .iter()
+            .rev()
+            .max_by_key(|m| m.score)
+            .expect("at least one mapping")
+    }
 
-    match (opponent_choice, desired_outcome) {
-        (opponent, DesiredOutcome::Draw) => opponent,
+    /// The lowest-scoring mapping, the first of them on a tie.
+    pub fn worst(&self) -> &MappingResult {
+        self.mappings
+            .iter()
+            .min_by_key(|m| m.score)
+            .expect("at least one mapping")
+    }
 
-        (Rock, Loss) | (Paper, Win) => Scissors,
-        (Rock, Win) | (Scissors, Loss) => Paper,
-        (Paper, Loss) | (Scissors, Win) => Rock,
+    /// Each response letter with the name of the option `result` plays for it.
+    fn names<'a>(&'a self, result: &'a MappingResult) -> impl Iterator<Item = (char, &'a str)> {
+        let names = result.mapping.iter().map(|&option| self.game.name(option));
+        self.letters.iter().copied().zip(names)
+    }
+
+    fn label(&self, result: &MappingResult) -> String {
+        self.names(result)
+            .map(|(letter, name)| format!("{letter} {name}"))
+            .join(", ")
+    }
+
+    pub fn to_json(&self) -> Value {
+        let result = |m: &MappingResult| {
+            let mapping = self
+                .names(m)
+                .map(|(letter, name)| (letter.to_string(), json!(name)))
+                .collect::<Map<_, _>>();
+
+            json!({
+                "mapping": mapping,
+                "wins": m.wins,
+                "draws": m.draws,
+                "losses": m.losses,
+                "score": m.score,
+            })
+        };
+
+        json!({
+            "rounds": self.mappings[0].wins + self.mappings[0].draws + self.mappings[0].losses,
+            "mappings": self.mappings.iter().map(result).collect::<Vec<_>>(),
+            "best": result(self.best()),
+            "worst": result(self.worst()),
+        })
     }
 }
 
-pub fn evaluate_round(opponent_choice: RpsChoice, player_choice: RpsChoice) -> i32 {
-    player_choice as i32
-        + match player_choice.cmp(&opponent_choice) {
-            Ordering::Less => 0,
-            Ordering::Equal => 3,
-            Ordering::Greater => 6,
+impl Display for GuideAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for letter in &self.letters {
+            write!(f, "{letter:<9}")?;
+        }
+
+        writeln!(
+            f,
+            "{:>6}{:>7}{:>8}{:>8}",
+            "wins", "draws", "losses", "score"
+        )?;
+
+        for m in &self.mappings {
+            for (_, name) in self.names(m) {
+                write!(f, "{name:<9}")?;
+            }
+
+            writeln!(
+                f,
+                "{:>6}{:>7}{:>8}{:>8}",
+                m.wins, m.draws, m.losses, m.score
+            )?;
         }
+
+        let (best, worst) = (self.best(), self.worst());
+        writeln!(f, "best    {} ({})", self.label(best), best.score)?;
+        writeln!(f, "worst   {} ({})", self.label(worst), worst.score)
+    }
+}
+
+/// Plays `game` with the guide's `choices`, read with `letters`, under every mapping of the
+/// response letters to distinct options.
+///
+/// Each round's response is only used as the position of its letter, so any option it was read as
+/// is replaced by the mapping's.
+pub fn analyze_guide(
+    game: &CyclicGame,
+    letters: &Letters<usize>,
+    choices: &[(usize, usize)],
+) -> GuideAnalysis {
+    let mappings = (0..game.len())
+        .permutations(letters.response.len())
+        .map(|mapping| {
+            let mut result = MappingResult {
+                mapping,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                score: 0,
+            };
+
+            for &(opponent, response) in choices {
+                let (outcome, score) = evaluate_round(game, opponent, result.mapping[response]);
+
+                match outcome {
+                    Outcome::Loss => result.losses += 1,
+                    Outcome::Draw => result.draws += 1,
+                    Outcome::Win => result.wins += 1,
+                }
+
+                result.score += score;
+            }
+
+            result
+        })
+        .collect();
+
+    GuideAnalysis {
+        game: game.clone(),
+        letters: letters.response.iter().map(|&(b, _)| b as char).collect(),
+        mappings,
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::*;
 
     static TEST_INPUT: &str = "A Y
 B X
@@ -112,11 +513,170 @@ C Z";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(TEST_INPUT.as_bytes()), 15);
+        assert_eq!(part1(&parse_guide(TEST_INPUT.as_bytes()).unwrap()), 15);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(TEST_INPUT.as_bytes()), 12);
+        assert_eq!(part2(&parse_guide(TEST_INPUT.as_bytes()).unwrap()), 12);
+    }
+
+    #[test]
+    fn malformed_round() {
+        let error = parse_guide(b"A Y\nB X\nC W").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (3, 3, Some('W')));
+    }
+
+    #[test]
+    fn rock_paper_scissors_rules() {
+        let game = CyclicGame::rock_paper_scissors();
+        let (rock, paper, scissors) = (0, 1, 2);
+
+        assert_eq!(game.outcome(paper, rock), Outcome::Win);
+        assert_eq!(game.outcome(scissors, paper), Outcome::Win);
+        assert_eq!(game.outcome(rock, scissors), Outcome::Win);
+        assert_eq!(game.choice_for(rock, Outcome::Loss), scissors);
+        assert_eq!(game.choice_for(scissors, Outcome::Win), rock);
+        assert_eq!(game.choice_for(paper, Outcome::Draw), paper);
+    }
+
+    #[test]
+    fn other_letters() {
+        let game = CyclicGame::rock_paper_scissors();
+        let choices = Letters::choices(&game, "rps", "RPS").unwrap();
+        let outcomes = Letters::outcomes(&game, "rps", "-=+").unwrap();
+
+        let rounds = parse_rounds(b"r P\np R\ns S", &choices).unwrap();
+        let aims = parse_rounds(b"r =\np -\ns +", &outcomes).unwrap();
+        let error = parse_rounds(b"r =\nx -", &outcomes).unwrap_err();
+
+        assert_eq!(score_as_choices(&game, &rounds), 15);
+        assert_eq!(score_as_outcomes(&game, &aims), 12);
+        assert_eq!((error.line, error.column, error.found), (2, 1, Some('x')));
+        assert!(Letters::choices(&game, "ABA", "XYZ").is_err());
+    }
+
+    #[test]
+    fn letters_must_fit_the_game() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+
+        assert!(Letters::choices(&game, "ABC", "XYZ").is_err());
+        assert!(Letters::choices(&game, "ABCDE", "XYZ").is_err());
+        assert!(Letters::choices(&game, "ABCDEF", "UVWXYZ").is_err());
+        assert!(Letters::outcomes(&game, "ABCDE", "VWXYZ").is_err());
+
+        let choices = Letters::choices(&game, "ABCDE", "VWXYZ").unwrap();
+        let outcomes = Letters::outcomes(&game, "ABCDE", "XYZ").unwrap();
+        let rounds = parse_rounds(b"A Y\nE Z", &choices).unwrap();
+        let aims = parse_rounds(b"A Z\nE X", &outcomes).unwrap();
+
+        // Spock beats Rock, and is the best of the two options that lose to Lizard.
+        assert_eq!(rounds, [(0, 3), (4, 4)]);
+        assert_eq!(aims, [(0, Outcome::Win), (4, Outcome::Loss)]);
+        assert_eq!(score_as_choices(&game, &rounds), (4 + 6) + (5 + 3));
+        assert_eq!(score_as_outcomes(&game, &aims), (4 + 6) + 4);
+        assert!(parse_rounds(b"F V", &choices).is_err());
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        let option = |name| (0..game.len()).find(|&i| game.name(i) == name).unwrap();
+        let beats = |a, b| game.outcome(option(a), option(b)) == Outcome::Win;
+
+        for (winner, losers) in [
+            ("Rock", ["Scissors", "Lizard"]),
+            ("Paper", ["Rock", "Spock"]),
+            ("Scissors", ["Paper", "Lizard"]),
+            ("Spock", ["Scissors", "Rock"]),
+            ("Lizard", ["Spock", "Paper"]),
+        ] {
+            for loser in losers {
+                assert!(beats(winner, loser) && !beats(loser, winner));
+            }
+        }
+
+        // Both Paper (2) and Spock (4) beat Rock, so the best win is Spock.
+        assert_eq!(
+            game.choice_for(option("Rock"), Outcome::Win),
+            option("Spock")
+        );
+        assert_eq!(game.score(option("Rock"), option("Spock")), 10);
+    }
+
+    #[test]
+    fn larger_games() {
+        let names = ["A", "B", "C", "D", "E", "F", "G"];
+        let game = CyclicGame::cyclic(&names)
+            .unwrap()
+            .with_scores(&[7, 6, 5, 4, 3, 2, 1], [-1, 0, 1])
+            .unwrap();
+
+        for a in 0..7 {
+            let wins = (0..7).filter(|&b| game.outcome(a, b) == Outcome::Win);
+            let losses = (0..7).filter(|&b| game.outcome(a, b) == Outcome::Loss);
+
+            assert_eq!((wins.count(), losses.count()), (3, 3));
+            assert_eq!(game.outcome(a, a), Outcome::Draw);
+        }
+
+        assert_eq!(game.score(0, 0), 7);
+        assert_eq!(game.choice_for(3, Outcome::Loss), 0);
+
+        assert!(CyclicGame::cyclic(&names[..4]).is_err());
+        // Everything beating the next option is a cycle, but not a balanced one.
+        let wins = (0..5).map(|a| (a, (a + 1) % 5)).collect::<Vec<_>>();
+        assert!(CyclicGame::new(&names[..5], &wins).is_err());
+        assert!(CyclicGame::new(&names[..3], &[(0, 1), (1, 0), (2, 0)]).is_err());
+    }
+
+    #[test]
+    fn analyze_example() {
+        let (rock, paper, scissors) = (0, 1, 2);
+
+        let game = CyclicGame::rock_paper_scissors();
+        let guide = parse_guide(TEST_INPUT.as_bytes()).unwrap();
+        let analysis = analyze_guide(&game, &puzzle_letters(&game), &guide.choices);
+        let mappings = analysis
+            .mappings
+            .iter()
+            .map(|m| (m.mapping.clone(), m.wins, m.draws, m.losses, m.score))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            mappings,
+            [
+                (vec![rock, paper, scissors], 1, 1, 1, 15),
+                (vec![rock, scissors, paper], 0, 0, 3, 6),
+                (vec![paper, rock, scissors], 0, 3, 0, 15),
+                (vec![paper, scissors, rock], 1, 1, 1, 15),
+                (vec![scissors, rock, paper], 1, 1, 1, 15),
+                (vec![scissors, paper, rock], 3, 0, 0, 24),
+            ]
+        );
+        assert_eq!(analysis.mappings[0].score, part1(&guide));
+        assert_eq!(analysis.best().mapping, [scissors, paper, rock]);
+        assert_eq!(analysis.worst().mapping, [rock, scissors, paper]);
+        assert_eq!(analysis.to_json()["rounds"], 3);
+        assert_eq!(analysis.to_json()["best"]["mapping"]["X"], "Scissors");
+        assert!(analysis
+            .to_string()
+            .ends_with("worst   X Rock, Y Scissors, Z Paper (6)\n"));
+    }
+
+    #[test]
+    fn analyze_other_games() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        let letters = Letters::choices(&game, "ABCDE", "VWXYZ").unwrap();
+        let rounds = parse_rounds(b"A Y\nE Z\nC V", &letters).unwrap();
+        let analysis = analyze_guide(&game, &letters, &rounds);
+
+        assert_eq!(analysis.mappings.len(), 120);
+        assert_eq!(analysis.mappings[0].score, score_as_choices(&game, &rounds));
+        // Paper, Scissors and Spock each win, since Spock can only beat one of Rock and Scissors.
+        assert_eq!(analysis.best().score, (2 + 6) + (3 + 6) + (4 + 6));
+        assert_eq!(evaluate_round(&game, 0, 3), (Outcome::Win, 10));
+        assert!(analysis.to_string().starts_with("V        W        X"));
     }
 }
@@ -1,29 +1,97 @@
 use itertools::Itertools;
 
-#[aoc(day3, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    input
-        .as_bytes()
+use crate::{error::ParseError, solution::Solution};
+
+const DAY: u8 = 3;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Rucksack Reorganization");
+
+    type Input = Vec<Vec<u8>>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rucksacks(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
+
+/// Reads the rucksacks, checking that each one's compartments and each group of three share an
+/// item.
+#[aoc_generator(day3)]
+pub fn parse_rucksacks(input: &[u8]) -> Result<Vec<Vec<u8>>, ParseError> {
+    let mut rucksacks = Vec::new();
+
+    for (i, line) in input
         .split(|&b| b == b'\n')
-        .filter(|s| !s.is_empty())
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+    {
+        let error = |index, expected| ParseError::unexpected(DAY, line, index, expected).on_line(i);
+
+        if let Some(index) = line.iter().position(|b| !b.is_ascii_alphabetic()) {
+            return Err(error(index, "item letter"));
+        }
+
+        if !line.len().is_multiple_of(2) {
+            return Err(error(line.len(), "item to fill both compartments equally"));
+        }
+
+        let (left, right) = line.split_at(line.len() / 2);
+        if find_common_item([left, right]).is_none() {
+            return Err(error(0, "rucksack with an item in both compartments"));
+        }
+
+        rucksacks.push(line.to_vec());
+
+        if let [.., a, b, c] = &rucksacks[..] {
+            if rucksacks.len().is_multiple_of(3)
+                && find_common_item([a, b, c].map(Vec::as_slice)).is_none()
+            {
+                return Err(error(0, "rucksack sharing an item with the two before it"));
+            }
+        }
+    }
+
+    Ok(rucksacks)
+}
+
+#[aoc(day3, part1)]
+pub fn run_part1(rucksacks: &[Vec<u8>]) -> i64 {
+    rucksacks
+        .iter()
         .map(|s| s.split_at(s.len() / 2))
-        .map(|(a, b)| find_common_item([a, b]))
+        .map(|(a, b)| find_common_item([a, b]).expect("parse_rucksacks checks the compartments"))
         .map(get_priority)
         .sum()
 }
 
 #[aoc(day3, part2)]
-pub fn run_part2(input: &str) -> i64 {
-    input
-        .as_bytes()
-        .split(|&b| b == b'\n')
+pub fn run_part2(rucksacks: &[Vec<u8>]) -> i64 {
+    rucksacks
+        .iter()
         .tuples()
-        .map(|(a, b, c)| find_common_item([a, b, c]))
+        .map(|(a, b, c)| {
+            find_common_item([a, b, c].map(Vec::as_slice))
+                .expect("parse_rucksacks checks the groups")
+        })
         .map(get_priority)
         .sum()
 }
 
-pub fn find_common_item<const SLICE_COUNT: usize>(slices: [&[u8]; SLICE_COUNT]) -> u8 {
+/// The first item of the last slice that is also in every other slice, if there is one.
+pub fn find_common_item<const SLICE_COUNT: usize>(slices: [&[u8]; SLICE_COUNT]) -> Option<u8> {
     let mut found_items = [0u8; (b'z' - b'A') as usize + 1];
 
     for slice in &slices[..(SLICE_COUNT - 1)] {
@@ -41,24 +109,25 @@ pub fn find_common_item<const SLICE_COUNT: usize>(slices: [&[u8]; SLICE_COUNT])
 
     for item in slices[SLICE_COUNT - 1] {
         if found_items[(*item - b'A') as usize] == (SLICE_COUNT - 1) as u8 {
-            return *item;
+            return Some(*item);
         }
     }
 
-    unreachable!()
+    None
 }
 
+/// The priority of an item letter, which [`parse_rucksacks`] has already checked.
 pub fn get_priority(item: u8) -> i64 {
-    match item {
-        b'a'..=b'z' => (item - b'a' + 1) as i64,
-        b'A'..=b'Z' => (item - b'A' + 27) as i64,
-        _ => unreachable!(),
+    if item.is_ascii_lowercase() {
+        (item - b'a' + 1) as i64
+    } else {
+        (item - b'A' + 27) as i64
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{run_part1, run_part2};
+    use super::*;
 
     static TEST_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -69,11 +138,38 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 157);
+        assert_eq!(
+            run_part1(&parse_rucksacks(TEST_INPUT.as_bytes()).unwrap()),
+            157
+        );
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 70);
+        assert_eq!(
+            run_part2(&parse_rucksacks(TEST_INPUT.as_bytes()).unwrap()),
+            70
+        );
+    }
+
+    #[test]
+    fn malformed_rucksack() {
+        let error = parse_rucksacks(b"vJrv\n\nab1d").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (3, 3, Some('1')));
+    }
+
+    #[test]
+    fn unsorted_rucksacks() {
+        let error = parse_rucksacks(b"abca\nabc").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (2, 4, None));
+
+        let error = parse_rucksacks(b"abca\nabcd").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (2, 1, Some('a')));
+
+        let error = parse_rucksacks(b"aa\nbb\ncc").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (3, 1, Some('c')));
+
+        assert_eq!(parse_rucksacks(b"aa\nbb").unwrap().len(), 2);
     }
 }
@@ -1,52 +1,107 @@
-#[aoc(day4, part1)]
-pub fn run_part1(input: &str) -> i64 {
+use crate::{error::ParseError, parse, solution::Solution};
+
+const DAY: u8 = 4;
+
+/// The section ranges of a pair of elves.
+pub type Assignment = ([u32; 2], [u32; 2]);
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Camp Cleanup");
+
+    type Input = Vec<Assignment>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_assignments(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
+
+#[aoc_generator(day4)]
+pub fn parse_assignments(input: &str) -> Result<Vec<Assignment>, ParseError> {
     input
         .as_bytes()
         .split(|&b| b == b'\n')
-        .filter(|s| !s.is_empty())
-        .filter(|pair| {
-            let comma = pair.iter().position(|&b| b == b',').unwrap();
-            let [a_start, a_end] = parse_pair(&pair[..comma]);
-            let [b_start, b_end] = parse_pair(&pair[(comma + 1)..]);
-
-            use std::cmp::Ordering::*;
-
-            matches!(
-                (a_start.cmp(&b_start), a_end.cmp(&b_end)),
-                (Less | Equal, Equal | Greater) | (Equal | Greater, Less | Equal)
-            )
-        })
-        .count() as i64
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, pair)| parse_assignment(pair).map_err(|e| e.on_line(i)))
+        .collect()
+}
+
+#[aoc(day4, part1)]
+pub fn run_part1(assignments: &[Assignment]) -> i64 {
+    let mut count = 0;
+
+    for &([a_start, a_end], [b_start, b_end]) in assignments {
+        use std::cmp::Ordering::*;
+
+        if matches!(
+            (a_start.cmp(&b_start), a_end.cmp(&b_end)),
+            (Less | Equal, Equal | Greater) | (Equal | Greater, Less | Equal)
+        ) {
+            count += 1;
+        }
+    }
+
+    count
 }
 
 #[aoc(day4, part2)]
-pub fn run_part2(input: &str) -> i64 {
-    input
-        .as_bytes()
-        .split(|&b| b == b'\n')
-        .filter(|s| !s.is_empty())
-        .filter(|pair| {
-            let comma = pair.iter().position(|&b| b == b',').unwrap();
-            let [a_start, a_end] = parse_pair(&pair[..comma]);
-            let [b_start, b_end] = parse_pair(&pair[(comma + 1)..]);
-
-            !((a_start < b_start && a_end < b_start) || (b_start < a_start && b_end < a_start))
-        })
-        .count() as i64
+pub fn run_part2(assignments: &[Assignment]) -> i64 {
+    let mut count = 0;
+
+    for &([a_start, a_end], [b_start, b_end]) in assignments {
+        if !((a_start < b_start && a_end < b_start) || (b_start < a_start && b_end < a_start)) {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+pub fn parse_assignment(pair: &[u8]) -> Result<Assignment, ParseError> {
+    let (first, comma) = parse_pair(pair, 0)?;
+
+    if pair.get(comma) != Some(&b',') {
+        return Err(ParseError::unexpected(DAY, pair, comma, "','"));
+    }
+
+    let (second, end) = parse_pair(pair, comma + 1)?;
+
+    if end != pair.len() {
+        return Err(ParseError::unexpected(DAY, pair, end, "end of line"));
+    }
+
+    Ok((first, second))
 }
 
-pub fn parse_pair(slice: &[u8]) -> [u8; 2] {
-    match slice {
-        [x, b'-', y] => [x - b'0', y - b'0'],
-        [x1, x2, b'-', y] => [(x1 - b'0') * 10 + (x2 - b'0'), y - b'0'],
-        [x, b'-', y1, y2] => [x - b'0', (y1 - b'0') * 10 + (y2 - b'0')],
-        [x1, x2, b'-', y1, y2] => [
-            (x1 - b'0') * 10 + (x2 - b'0'),
-            (y1 - b'0') * 10 + (y2 - b'0'),
-        ],
-
-        _ => unreachable!(),
+/// Parses the range at `index`, returning it and the index after it.
+pub fn parse_pair(slice: &[u8], index: usize) -> Result<([u32; 2], usize), ParseError> {
+    let (start, dash) = parse_section(slice, index)?;
+
+    if slice.get(dash) != Some(&b'-') {
+        return Err(ParseError::unexpected(DAY, slice, dash, "'-'"));
     }
+
+    let (end, next) = parse_section(slice, dash + 1)?;
+
+    Ok(([start, end], next))
+}
+
+/// Parses the section ID at `index`, returning it and the index after it.
+fn parse_section(slice: &[u8], index: usize) -> Result<(u32, usize), ParseError> {
+    parse::number_at(DAY, slice, index)
 }
 
 #[cfg(test)]
@@ -62,11 +117,18 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 2);
+        assert_eq!(run_part1(&parse_assignments(TEST_INPUT).unwrap()), 2);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 4);
+        assert_eq!(run_part2(&parse_assignments(TEST_INPUT).unwrap()), 4);
+    }
+
+    #[test]
+    fn malformed_pair() {
+        let error = parse_assignments("2-4,6-8\n2-3;4-5").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (2, 4, Some(';')));
     }
 }
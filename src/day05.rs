@@ -1,10 +1,13 @@
 use std::fmt::Display;
 
-#[derive(Debug)]
+use crate::{error::ParseError, parse, solution::Solution};
+
+const DAY: u8 = 5;
+
+/// The stacks of crates, each listed bottom to top.
+#[derive(Debug, Clone, Default)]
 pub struct Stacks {
-    stacks: [[u8; 64]; 16],
-    stack_sizes: [u8; 16],
-    stack_count: u8,
+    stacks: Vec<Vec<u8>>,
 }
 
 impl Stacks {
@@ -13,109 +16,78 @@ impl Stacks {
         Self::default()
     }
 
-    fn stack(&self, index: u8) -> *const u8 {
-        (&self.stacks[index as usize]) as *const u8
+    /// Adds `new_crate` under the crates already on stack `index`, as the drawing is read top down.
+    pub fn push_crate(&mut self, index: usize, new_crate: u8) {
+        self.ensure_stacks(index + 1);
+        self.stacks[index].push(new_crate);
     }
 
-    fn stack_mut(&mut self, index: u8) -> *mut u8 {
-        (&mut self.stacks[index as usize]) as *mut u8
-    }
-
-    fn stack_end(&self, index: u8) -> *const u8 {
-        unsafe { self.stack(index).add(self.stack_size(index) as _) }
-    }
-
-    fn stack_end_mut(&mut self, index: u8) -> *mut u8 {
-        unsafe { self.stack_mut(index).add(self.stack_size(index) as _) }
-    }
-
-    fn stack_size(&self, stack: u8) -> usize {
-        self.stack_sizes[stack as usize] as usize
+    fn ensure_stacks(&mut self, count: usize) {
+        if self.stacks.len() < count {
+            self.stacks.resize_with(count, Vec::new);
+        }
     }
 
-    fn stack_size_mut(&mut self, stack: u8) -> &mut u8 {
-        &mut self.stack_sizes[stack as usize]
+    /// How many crates are on each stack.
+    pub fn heights(&self) -> Vec<usize> {
+        self.stacks.iter().map(Vec::len).collect()
     }
 
-    pub fn push_crate(&mut self, index: u8, new_crate: u8) {
-        self.stack_sizes[index as usize] -= 1;
-
-        unsafe {
-            self.stack_end_mut(index).write(new_crate);
+    /// Turns the stacks the right way up once the whole drawing has been read.
+    pub fn flip_stacks(&mut self) {
+        for stack in &mut self.stacks {
+            stack.reverse();
         }
     }
 
-    pub fn slide_stacks_back(&mut self) {
-        for stack_idx in 0..self.stack_count {
-            let src_start = self.stack_size(stack_idx);
-            let count = 64 - src_start;
-
-            self.stacks[stack_idx as usize].copy_within(src_start..(src_start + count), 0);
-
-            *self.stack_size_mut(stack_idx) = count as u8;
+    /// The stacks `from` and `to`, which must differ.
+    fn pair_mut(&mut self, from: usize, to: usize) -> (&mut Vec<u8>, &mut Vec<u8>) {
+        if from < to {
+            let (left, right) = self.stacks.split_at_mut(to);
+            (&mut left[from], &mut right[0])
+        } else {
+            let (left, right) = self.stacks.split_at_mut(from);
+            (&mut right[0], &mut left[to])
         }
     }
 
-    pub fn move_crates_p1(&mut self, count: usize, from: u8, to: u8) {
-        let (from_len, to_len) = (self.stack_size(from), self.stack_size(to));
-
-        let mut temp_buffer = [0u8; 64];
-
-        for (i, b) in self.stacks[from as usize][(from_len - count)..from_len]
-            .iter()
-            .rev()
-            .enumerate()
-        {
-            temp_buffer[i] = *b;
+    pub fn move_crates_p1(&mut self, count: usize, from: usize, to: usize) {
+        if from == to {
+            let stack = &mut self.stacks[from];
+            let start = stack.len() - count;
+            stack[start..].reverse();
+            return;
         }
 
-        self.stacks[to as usize][to_len..(to_len + count)].copy_from_slice(&temp_buffer[..count]);
+        let (from, to) = self.pair_mut(from, to);
+        let start = from.len() - count;
 
-        *self.stack_size_mut(from) -= count as u8;
-        *self.stack_size_mut(to) += count as u8;
+        to.extend(from.drain(start..).rev());
     }
 
-    pub fn move_crates_p2(&mut self, count: usize, from: u8, to: u8) {
-        let (from_len, to_len) = (self.stack_size(from), self.stack_size(to));
-
-        let mut temp_buffer = [0u8; 64];
-
-        temp_buffer[..count]
-            .copy_from_slice(&self.stacks[from as usize][(from_len - count)..from_len]);
+    pub fn move_crates_p2(&mut self, count: usize, from: usize, to: usize) {
+        if from == to {
+            return;
+        }
 
-        self.stacks[to as usize][to_len..(to_len + count)].copy_from_slice(&temp_buffer[..count]);
+        let (from, to) = self.pair_mut(from, to);
+        let start = from.len() - count;
 
-        *self.stack_size_mut(from) -= count as u8;
-        *self.stack_size_mut(to) += count as u8;
+        to.extend(from.drain(start..));
     }
 
+    /// The crate on top of each stack, skipping any left empty.
     pub fn get_top_crates(&self) -> impl Iterator<Item = u8> + '_ {
-        (0..self.stack_count)
-            .map(|stack_idx| unsafe { self.stack_end(stack_idx).offset(-1).read() })
-    }
-}
-
-impl Default for Stacks {
-    fn default() -> Self {
-        Self {
-            stacks: [[0u8; 64]; 16],
-            stack_sizes: [64u8; 16],
-            stack_count: 0u8,
-        }
+        self.stacks.iter().filter_map(|stack| stack.last().copied())
     }
 }
 
 impl Display for Stacks {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (i, stack) in self
-            .stacks
-            .iter()
-            .take(self.stack_count as usize)
-            .enumerate()
-        {
+        for (i, stack) in self.stacks.iter().enumerate() {
             f.write_fmt(format_args!(
                 "{i} {}\n",
-                std::str::from_utf8(&stack[..(self.stack_sizes[i] as usize)]).unwrap()
+                std::str::from_utf8(stack).unwrap()
             ))?;
         }
 
@@ -123,59 +95,96 @@ impl Display for Stacks {
     }
 }
 
-#[aoc(day5, part1)]
-pub fn run_part1(input: &str) -> String {
-    let input = input.as_bytes();
-    let (mut stacks, offset, double_digit_stacks) = read_crates(input);
+/// A single `move N from A to B` step, with zero-based stack indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// The starting stacks and the moves the crane makes.
+#[derive(Debug, Clone)]
+pub struct Procedure {
+    pub stacks: Stacks,
+    pub moves: Vec<Move>,
+}
 
-    stacks.slide_stacks_back();
+pub struct Day05;
 
-    let stacks = if double_digit_stacks {
-        read_instructions_double_stacks(&input[offset..], stacks)
-    } else {
-        read_instructions_single_stacks(&input[offset..], stacks)
-    };
+impl Solution for Day05 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Supply Stacks");
 
-    let mut out_buffer = [0u8; 64];
+    type Input = Procedure;
+    type Output1 = String;
+    type Output2 = String;
 
-    for (i, top_crate) in stacks.get_top_crates().enumerate() {
-        out_buffer[i] = top_crate;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_procedure(input.as_bytes())
     }
 
-    unsafe { std::str::from_utf8_unchecked(&out_buffer[..(stacks.stack_count as usize)]) }
-        .to_owned()
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
 }
 
-#[aoc(day5, part2)]
-pub fn run_part2(input: &str) -> String {
-    let input = input.as_bytes();
-    let (mut stacks, offset, double_digit_stacks) = read_crates(input);
+#[aoc_generator(day5)]
+pub fn parse_procedure(input: &[u8]) -> Result<Procedure, ParseError> {
+    let (mut stacks, offset, double_digit_stacks) = read_crates(input)?;
+
+    stacks.flip_stacks();
 
-    stacks.slide_stacks_back();
+    let header_lines = input[..offset].iter().filter(|&&b| b == b'\n').count();
 
-    let stacks = if double_digit_stacks {
-        read_instructions_double_stacks(&input[offset..], stacks)
+    let heights = stacks.heights();
+    let moves = if double_digit_stacks {
+        read_instructions_double_stacks(&input[offset..], heights)
     } else {
-        read_instructions_single_stacks(&input[offset..], stacks)
-    };
+        read_instructions_single_stacks(&input[offset..], heights)
+    }
+    .map_err(|e| e.on_line(header_lines))?;
+
+    Ok(Procedure { stacks, moves })
+}
+
+#[aoc(day5, part1)]
+pub fn run_part1(procedure: &Procedure) -> String {
+    let mut stacks = procedure.stacks.clone();
 
-    let mut out_buffer = [0u8; 64];
+    for &Move { count, from, to } in &procedure.moves {
+        stacks.move_crates_p1(count, from, to);
+    }
 
-    for (i, top_crate) in stacks.get_top_crates().enumerate() {
-        out_buffer[i] = top_crate;
+    top_crates(&stacks)
+}
+
+#[aoc(day5, part2)]
+pub fn run_part2(procedure: &Procedure) -> String {
+    let mut stacks = procedure.stacks.clone();
+
+    for &Move { count, from, to } in &procedure.moves {
+        stacks.move_crates_p2(count, from, to);
     }
 
-    unsafe { std::str::from_utf8_unchecked(&out_buffer[..(stacks.stack_count as usize)]) }
-        .to_owned()
+    top_crates(&stacks)
+}
+
+fn top_crates(stacks: &Stacks) -> String {
+    stacks.get_top_crates().map(char::from).collect()
 }
 
-fn read_crates(input: &[u8]) -> (Stacks, usize, bool) {
+fn read_crates(input: &[u8]) -> Result<(Stacks, usize, bool), ParseError> {
     let mut offset = 0;
     let mut current_index = 0;
     let mut stacks = Stacks::new();
 
     loop {
-        let chunk = &input[offset..(offset + 4)];
+        let chunk = input.get(offset..(offset + 4)).unwrap_or(&input[offset..]);
 
         let reached_line_end = match chunk {
             [b'[', stacked_crate @ b'A'..=b'Z', b']', last @ (b' ' | b'\n')] => {
@@ -183,81 +192,163 @@ fn read_crates(input: &[u8]) -> (Stacks, usize, bool) {
                 *last == b'\n'
             }
             [b' ', b' ', b' ', last @ (b' ' | b'\n')] => *last == b'\n',
-            [b' ', b'1'..=b'9', _, _] => {
+            [b' ', b'1'..=b'9', _, _] if current_index == 0 => {
                 break;
             }
-            _ => unreachable!(),
+            _ => {
+                let column = match chunk {
+                    [b'[', b'A'..=b'Z', b']', ..] | [b' ', b' ', b' ', ..] => 3,
+                    [b'[', b'A'..=b'Z', ..] | [b' ', b' ', ..] => 2,
+                    [b'[' | b' ', ..] => 1,
+                    _ => 0,
+                };
+
+                return Err(ParseError::at_offset(
+                    DAY,
+                    input,
+                    offset + column,
+                    "crate or stack number",
+                ));
+            }
         };
 
         offset += 4;
 
         if reached_line_end {
-            stacks.stack_count = current_index + 1;
+            stacks.ensure_stacks(current_index + 1);
             current_index = 0;
         } else {
             current_index += 1;
         }
     }
 
-    offset += &input[offset..].iter().position(|&b| b == b'\n').unwrap();
+    let numbers_start = offset;
+
+    offset += &input[offset..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or_else(|| ParseError::at_offset(DAY, input, input.len(), "move instructions"))?;
+
+    // Every numbered stack exists, even if no crate is drawn on it.
+    let numbers = input[numbers_start..offset].split(|&b| b == b' ');
+    stacks.ensure_stacks(numbers.filter(|n| !n.is_empty()).count());
 
     let double_digit_stacks = !matches!(
-        &input[(offset - 3)..=offset],
-        [b' ', b'0'..=b'9', b' ', b'\n']
+        input[..=offset].last_chunk(),
+        Some([b' ', b'0'..=b'9', b' ', b'\n'])
     );
 
-    (stacks, offset + 2, double_digit_stacks)
+    Ok((stacks, offset + 2, double_digit_stacks))
 }
 
-fn read_instructions_single_stacks(input: &[u8], mut stacks: Stacks) -> Stacks {
-    for line in input.split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+fn read_instructions_single_stacks(
+    input: &[u8],
+    mut heights: Vec<usize>,
+) -> Result<Vec<Move>, ParseError> {
+    let mut moves = Vec::new();
+
+    for (i, line) in input
+        .split(|&b| b == b'\n')
+        .enumerate()
+        .filter(|(_, l)| !l.is_empty())
+    {
         const TO_LEN: usize = " TO ".len();
 
-        let (count, offset) = get_crate_count(line);
+        let (count, offset) = get_crate_count(line).map_err(|e| e.on_line(i))?;
+        let to_offset = offset + 1 + TO_LEN;
 
-        let from = line[offset] - b'0' - 1;
-        let to = line[offset + 1 + TO_LEN] - b'0' - 1;
+        let from = parse_stack_digit(line, offset).map_err(|e| e.on_line(i))?;
+        let to = parse_stack_digit(line, to_offset).map_err(|e| e.on_line(i))?;
 
-        stacks.move_crates_p2(count, from, to);
+        let step = Move { count, from, to };
+        check_move(&mut heights, line, step, [offset, to_offset]).map_err(|e| e.on_line(i))?;
+        moves.push(step);
     }
 
-    stacks
+    Ok(moves)
 }
 
-fn read_instructions_double_stacks(input: &[u8], mut stacks: Stacks) -> Stacks {
-    for line in input.split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+fn read_instructions_double_stacks(
+    input: &[u8],
+    mut heights: Vec<usize>,
+) -> Result<Vec<Move>, ParseError> {
+    let mut moves = Vec::new();
+
+    for (i, line) in input
+        .split(|&b| b == b'\n')
+        .enumerate()
+        .filter(|(_, l)| !l.is_empty())
+    {
         const TO_LEN: usize = " TO ".len();
 
-        let (count, offset) = get_crate_count(line);
-        let (from, width) = parse_one_or_two_digit_number(&line[offset..(offset + 2)]);
+        let (count, from_offset) = get_crate_count(line).map_err(|e| e.on_line(i))?;
+        let (from, offset) = parse_stack_number(line, from_offset).map_err(|e| e.on_line(i))?;
+        let to_offset = offset + TO_LEN;
+        let (to, _) = parse_stack_number(line, to_offset).map_err(|e| e.on_line(i))?;
 
-        let offset = offset + width + TO_LEN;
+        let step = Move { count, from, to };
+        check_move(&mut heights, line, step, [from_offset, to_offset]).map_err(|e| e.on_line(i))?;
+        moves.push(step);
+    }
 
-        let (to, _) = parse_one_or_two_digit_number(&line[offset..(offset + 2)]);
+    Ok(moves)
+}
 
-        stacks.move_crates_p2(count, from, to);
+/// Checks that `step` names existing stacks and moves no more crates than `from` holds, then
+/// applies it to the stack `heights`. `stack_offsets` locates the two stack numbers in `line`.
+fn check_move(
+    heights: &mut [usize],
+    line: &[u8],
+    step: Move,
+    stack_offsets: [usize; 2],
+) -> Result<(), ParseError> {
+    const MOVE_LEN: usize = b"move ".len();
+
+    for (stack, offset) in [step.from, step.to].into_iter().zip(stack_offsets) {
+        if stack >= heights.len() {
+            return Err(ParseError::unexpected(DAY, line, offset, "existing stack"));
+        }
+    }
+
+    if step.count > heights[step.from] {
+        return Err(ParseError::unexpected(
+            DAY,
+            line,
+            MOVE_LEN,
+            "at most as many crates as the stack holds",
+        ));
     }
 
-    stacks
+    heights[step.from] -= step.count;
+    heights[step.to] += step.count;
+
+    Ok(())
 }
 
-fn get_crate_count(input: &[u8]) -> (usize, usize) {
+fn get_crate_count(input: &[u8]) -> Result<(usize, usize), ParseError> {
     const MOVE_LEN: usize = b"move ".len();
     const FROM_LEN: usize = b" from ".len();
 
-    let (num, width) = parse_one_or_two_digit_number(&input[MOVE_LEN..(MOVE_LEN + 2)]);
-    (num as usize, width + MOVE_LEN + FROM_LEN)
+    let (num, offset) = parse::number_at(DAY, input, MOVE_LEN)?;
+    Ok((num, offset + FROM_LEN))
 }
 
-fn parse_one_or_two_digit_number(input: &[u8]) -> (u8, usize) {
-    match &input[..2] {
-        [x @ b'0'..=b'9', b' ' | b'\n'] => (*x - b'0', 1),
-        [x @ b'0'..=b'9', y @ b'0'..=b'9'] => ((*x - b'0') * 10 + (*y - b'0'), 2),
-        _ => unreachable!(),
+fn parse_stack_digit(line: &[u8], index: usize) -> Result<usize, ParseError> {
+    match line.get(index) {
+        Some(x @ b'1'..=b'9') => Ok((x - b'0' - 1) as usize),
+        _ => Err(ParseError::unexpected(DAY, line, index, "stack number")),
     }
 }
 
-/* #[cfg(test)]
+/// Parses the stack number at `index` into a stack index, returning it and the index after it.
+fn parse_stack_number(line: &[u8], index: usize) -> Result<(usize, usize), ParseError> {
+    match parse::number_at::<usize>(DAY, line, index)? {
+        (0, _) => Err(ParseError::unexpected(DAY, line, index, "stack number")),
+        (stack, offset) => Ok((stack - 1, offset)),
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -273,11 +364,54 @@ move 1 from 1 to 2";
 
     #[test]
     fn part1_example() {
-        assert_eq!(&run_part1(TEST_INPUT), "CMZ");
+        assert_eq!(
+            run_part1(&parse_procedure(TEST_INPUT.as_bytes()).unwrap()),
+            "CMZ"
+        );
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 4);
+        assert_eq!(
+            run_part2(&parse_procedure(TEST_INPUT.as_bytes()).unwrap()),
+            "MCD"
+        );
+    }
+
+    #[test]
+    fn impossible_moves() {
+        let error = parse_procedure(b" 1\n\nmove 1 from 1 to 1").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (3, 6, Some('1')));
+
+        let error = parse_procedure(b"[A]\n 1\n\nmove 1 from 3 to 1").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (4, 13, Some('3')));
+
+        let error = parse_procedure(b"[A]\n 1  2 \n\nmove 1 from 1 to 2\nmove 2 from 2 to 2");
+        let error = error.unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (5, 6, Some('2')));
+
+        let input = TEST_INPUT.replace("move 1 from 1 to 2", "move 1 from 1 to 12");
+        let error = parse_procedure(input.as_bytes()).unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (9, 18, Some('1')));
+
+        let procedure = parse_procedure(b"    [B]\n 1   2   3 \n\nmove 1 from 2 to 3").unwrap();
+        assert_eq!(run_part1(&procedure), "B");
     }
-} */
+
+    #[test]
+    fn tall_and_wide_stacks() {
+        let mut rows = vec!["[A] ".repeat(20).trim_end().to_owned(); 70];
+        rows[0] = format!("{}[Z]", "    ".repeat(19));
+        let numbers = (1..=20).map(|i| format!("{i:^3}")).collect::<Vec<_>>();
+
+        let input = format!(
+            "{}\n{}\n\nmove 69 from 20 to 19",
+            rows.join("\n"),
+            numbers.join(" ")
+        );
+        let procedure = parse_procedure(input.as_bytes()).unwrap();
+
+        assert_eq!(run_part1(&procedure), "A".repeat(20));
+        assert_eq!(run_part2(&procedure), format!("{}ZA", "A".repeat(18)));
+    }
+}
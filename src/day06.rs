@@ -1,21 +1,46 @@
+use crate::{error::ParseError, solution::Solution};
+
+const DAY: u8 = 6;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Tuning Trouble");
+
+    type Input = Vec<u8>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_signal(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1_naive(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2_naive(input)
+    }
+}
+
+#[aoc_generator(day6)]
+pub fn parse_signal(input: &[u8]) -> Result<Vec<u8>, ParseError> {
+    match input.iter().position(|b| !b.is_ascii_lowercase()) {
+        Some(index) => Err(ParseError::at_offset(DAY, input, index, "lowercase letter")),
+        None => Ok(input.to_vec()),
+    }
+}
+
 #[aoc(day6, part1, naive)]
-pub fn run_part1_naive(input: &str) -> i64 {
-    input
-        .as_bytes()
-        .windows(4)
-        .take_while(|w| !is_unique(w))
-        .count() as i64
-        + 4
+pub fn run_part1_naive(signal: &[u8]) -> i64 {
+    signal.windows(4).take_while(|w| !is_unique(w)).count() as i64 + 4
 }
 
 #[aoc(day6, part2, naive)]
-pub fn run_part2_naive(input: &str) -> i64 {
-    input
-        .as_bytes()
-        .windows(14)
-        .take_while(|w| !is_unique(w))
-        .count() as i64
-        + 14
+pub fn run_part2_naive(signal: &[u8]) -> i64 {
+    signal.windows(14).take_while(|w| !is_unique(w)).count() as i64 + 14
 }
 
 fn is_unique(slice: &[u8]) -> bool {
@@ -38,18 +63,25 @@ mod tests {
 
     #[test]
     fn part1_naive_example() {
-        assert_eq!(run_part1_naive("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-        assert_eq!(run_part1_naive("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-        assert_eq!(run_part1_naive("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-        assert_eq!(run_part1_naive("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+        assert_eq!(run_part1_naive(b"bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
+        assert_eq!(run_part1_naive(b"nppdvjthqldpwncqszvftbrmjlhg"), 6);
+        assert_eq!(run_part1_naive(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
+        assert_eq!(run_part1_naive(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
     }
 
     #[test]
     fn part2_naive_example() {
-        assert_eq!(run_part2_naive("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-        assert_eq!(run_part2_naive("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-        assert_eq!(run_part2_naive("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-        assert_eq!(run_part2_naive("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-        assert_eq!(run_part2_naive("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+        assert_eq!(run_part2_naive(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
+        assert_eq!(run_part2_naive(b"bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
+        assert_eq!(run_part2_naive(b"nppdvjthqldpwncqszvftbrmjlhg"), 23);
+        assert_eq!(run_part2_naive(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
+        assert_eq!(run_part2_naive(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+    }
+
+    #[test]
+    fn malformed_signal() {
+        let error = parse_signal(b"abcD").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (1, 4, Some('D')));
     }
 }
@@ -1,13 +1,15 @@
-use std::ops::Index;
+use crate::{error::ParseError, parse, solution::Solution};
+
+const DAY: u8 = 7;
 
 #[derive(Debug)]
-struct Item<'a> {
+struct Item {
     parent: usize,
-    name: &'a str,
+    name: String,
     item_type: ItemType,
 }
 
-impl<'a> Item<'a> {
+impl Item {
     pub fn size(&self) -> i64 {
         match &self.item_type {
             ItemType::File { size } => *size,
@@ -23,27 +25,27 @@ enum ItemType {
 }
 
 #[derive(Debug)]
-struct Drive<'a> {
-    items: Vec<Item<'a>>,
+pub struct Drive {
+    items: Vec<Item>,
 }
 
-impl<'a> Index<(usize, &'a str)> for Drive<'a> {
-    type Output = usize;
+impl Drive {
+    fn new() -> Self {
+        Drive { items: Vec::new() }
+    }
 
-    fn index(&self, (index, name): (usize, &str)) -> &Self::Output {
+    /// The directory called `name` inside the directory at `index`, if it has been listed.
+    fn subdirectory(&self, index: usize, name: &str) -> Option<usize> {
         let node = &self.items[index];
         let ItemType::Directory { total_size: _, items } = &node.item_type else { unreachable!()};
 
-        items.iter().find(|i| self.items[**i].name == name).unwrap()
-    }
-}
-
-impl<'a> Drive<'a> {
-    pub fn new() -> Self {
-        Drive { items: Vec::new() }
+        items.iter().copied().find(|&i| {
+            let item = &self.items[i];
+            item.name == name && matches!(item.item_type, ItemType::Directory { .. })
+        })
     }
 
-    pub fn add_item(&mut self, folder: usize, item: Item<'a>) {
+    fn add_item(&mut self, folder: usize, item: Item) {
         let item_index = self.items.len();
 
         let file_size = if let ItemType::File { size } = &item.item_type {
@@ -97,52 +99,34 @@ impl<'a> Drive<'a> {
     }
 }
 
-#[aoc(day7, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    let drive = load_drive(input);
+pub struct Day07;
 
-    drive
-        .items
-        .into_iter()
-        .filter_map(|i| match i.item_type {
-            ItemType::Directory {
-                total_size: size @ 0..=100_000,
-                ..
-            } => Some(size),
-            _ => None,
-        })
-        .sum()
-}
+impl Solution for Day07 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("No Space Left On Device");
 
-#[aoc(day7, part2)]
-pub fn run_part2(input: &str) -> i64 {
-    let drive = load_drive(input);
+    type Input = Drive;
+    type Output1 = i64;
+    type Output2 = i64;
 
-    const TOTAL_SPACE: i64 = 70_000_000;
-    const NEEDED_SPACE: i64 = 30_000_000;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_drive(input)
+    }
 
-    let used_space = drive.items[0].size();
-    let unused_space = TOTAL_SPACE - used_space;
-    let space_to_delete = NEEDED_SPACE - unused_space;
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
 
-    drive
-        .items
-        .into_iter()
-        .filter_map(|i| match i.item_type {
-            ItemType::Directory {
-                total_size,
-                ..
-            } if total_size >= space_to_delete => Some(total_size),
-            _ => None,
-        })
-        .min()
-        .unwrap()
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
 }
 
-fn load_drive(input: &str) -> Drive<'_> {
+#[aoc_generator(day7)]
+pub fn load_drive(input: &str) -> Result<Drive, ParseError> {
     let root = Item {
         parent: 0,
-        name: "/",
+        name: "/".to_owned(),
         item_type: ItemType::Directory {
             total_size: 0,
             items: Vec::new(),
@@ -156,18 +140,24 @@ fn load_drive(input: &str) -> Drive<'_> {
 
     let mut ls_mode = false;
 
-    for line in input.split('\n') {
-        if ls_mode && &line[..1] == "$" {
+    for (line_idx, line) in input.split('\n').enumerate() {
+        let error = |index, expected| {
+            ParseError::unexpected(DAY, line.as_bytes(), index, expected).on_line(line_idx)
+        };
+
+        if ls_mode && line.starts_with('$') {
             ls_mode = false;
         }
 
         if ls_mode {
-            let (dir_or_size, name) = line.split_once(' ').unwrap();
+            let (dir_or_size, name) = line
+                .split_once(' ')
+                .ok_or_else(|| error(line.len(), "' ' between a size or 'dir' and a name"))?;
 
             let item = if dir_or_size == "dir" {
                 Item {
                     parent: cwd,
-                    name,
+                    name: name.to_owned(),
                     item_type: ItemType::Directory {
                         total_size: 0,
                         items: Vec::new(),
@@ -176,40 +166,87 @@ fn load_drive(input: &str) -> Drive<'_> {
             } else {
                 Item {
                     parent: cwd,
-                    name,
+                    name: name.to_owned(),
                     item_type: ItemType::File {
-                        size: dir_or_size.parse().unwrap(),
+                        size: parse::exact(dir_or_size.as_bytes()).map_err(|e| {
+                            e.into_parse_error(DAY, dir_or_size.as_bytes())
+                                .on_line(line_idx)
+                        })?,
                     },
                 }
             };
 
             drive.add_item(cwd, item);
-        } else {
-            match &line[..4] {
-                "$ ls" => {
-                    ls_mode = true;
+        } else if line == "$ ls" {
+            ls_mode = true;
+        } else if let Some(dir_name) = line.strip_prefix("$ cd ") {
+            match dir_name {
+                "/" => {
+                    cwd = 0;
                 }
-                "$ cd" => {
-                    let dir_name = &line[5..];
-
-                    match dir_name {
-                        "/" => {
-                            cwd = 0;
-                        }
-                        ".." => {
-                            cwd = drive.items[cwd].parent;
-                        }
-                        _ => {
-                            cwd = drive[(cwd, dir_name)];
-                        }
-                    }
+                ".." => {
+                    cwd = drive.items[cwd].parent;
+                }
+                _ => {
+                    cwd = drive
+                        .subdirectory(cwd, dir_name)
+                        .ok_or_else(|| error(5, "directory listed in the current one"))?;
                 }
-                _ => {}
             }
+        } else {
+            // Point past the longest prefix of either command, where the line goes wrong.
+            let matched = ["$ cd ", "$ ls"]
+                .iter()
+                .map(|command| {
+                    let pairs = command.bytes().zip(line.bytes());
+                    pairs.take_while(|(a, b)| a == b).count()
+                })
+                .max()
+                .unwrap_or(0);
+
+            return Err(error(matched, "'$ cd' or '$ ls'"));
         }
     }
 
+    Ok(drive)
+}
+
+#[aoc(day7, part1)]
+pub fn run_part1(drive: &Drive) -> i64 {
     drive
+        .items
+        .iter()
+        .filter_map(|i| match i.item_type {
+            ItemType::Directory {
+                total_size: size @ 0..=100_000,
+                ..
+            } => Some(size),
+            _ => None,
+        })
+        .sum()
+}
+
+#[aoc(day7, part2)]
+pub fn run_part2(drive: &Drive) -> i64 {
+    const TOTAL_SPACE: i64 = 70_000_000;
+    const NEEDED_SPACE: i64 = 30_000_000;
+
+    let used_space = drive.items[0].size();
+    let unused_space = TOTAL_SPACE - used_space;
+    let space_to_delete = NEEDED_SPACE - unused_space;
+
+    drive
+        .items
+        .iter()
+        .filter_map(|i| match i.item_type {
+            ItemType::Directory {
+                total_size,
+                ..
+            } if total_size >= space_to_delete => Some(total_size),
+            _ => None,
+        })
+        .min()
+        .unwrap()
 }
 
 #[cfg(test)]
@@ -242,11 +279,32 @@ $ ls
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 95437);
+        assert_eq!(run_part1(&load_drive(TEST_INPUT).unwrap()), 95437);
     }
 
     #[test]
     fn part2_naive_example() {
-        assert_eq!(run_part2(TEST_INPUT), 24933642);
+        assert_eq!(run_part2(&load_drive(TEST_INPUT).unwrap()), 24933642);
+    }
+
+    #[test]
+    fn malformed_terminal() {
+        let error = load_drive("$ cd /\n$ ls\n\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (3, 1, None));
+
+        let error = load_drive("$").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (1, 2, None));
+
+        let error = load_drive("$ ls\nfoo").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (2, 4, None));
+
+        let error = load_drive("$ cd /\n$ rm -rf a").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (2, 3, Some('r')));
+
+        let error = load_drive("$ ls\n12 a\n$ cd a").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (3, 6, Some('a')));
+
+        let error = load_drive("$ ls\ndir a\n$ cd b").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (3, 6, Some('b')));
     }
 }
@@ -1,25 +1,57 @@
 use std::cmp::max;
 
-#[cfg(not(test))]
-const ROW_LEN: usize = 99;
-#[cfg(test)]
-const ROW_LEN: usize = 5;
+use crate::{
+    error::ParseError,
+    grid::{Grid, Pos},
+    solution::Solution,
+};
+
+const DAY: u8 = 8;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Treetop Tree House");
+
+    type Input = Grid<u8>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_trees(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
+
+#[aoc_generator(day8)]
+pub fn parse_trees(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(DAY, input, |b| b.is_ascii_digit(), "tree height")
+}
 
 #[aoc(day8, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    let mut marked = [false; ROW_LEN * ROW_LEN];
-    let mut rows = input.as_bytes().split(|&b| b == b'\n').enumerate();
+pub fn run_part1(forest: &Grid<u8>) -> i64 {
+    let (width, height) = (forest.width(), forest.height());
 
-    let (_, first_row) = rows.by_ref().next().unwrap();
+    // Without an interior, every tree is on the edge.
+    if width <= 2 || height <= 2 {
+        return forest.len() as i64;
+    }
 
-    let mut buffer = [0u8; ROW_LEN];
-    buffer[..ROW_LEN].copy_from_slice(first_row);
-    let mut top_visibility = buffer;
+    let mut marked = vec![false; forest.len()];
+    let mut top_visibility = forest.row(0).to_vec();
 
     // Top and bottom rows.
-    let mut total_visible = ROW_LEN * 2;
+    let mut total_visible = width * 2;
 
-    for (row_idx, row) in rows.by_ref().take(ROW_LEN - 2) {
+    for (row_idx, row) in forest.rows().enumerate().skip(1).take(height - 2) {
         // Left and right trees.
         total_visible += 2;
 
@@ -29,10 +61,10 @@ pub fn run_part1(input: &str) -> i64 {
             top_visibility[0] = row[0];
         }
 
-        for (i, tree) in row.iter().copied().enumerate().skip(1).take(ROW_LEN - 2) {
+        for (i, tree) in row.iter().copied().enumerate().skip(1).take(width - 2) {
             if tree > top_visibility[i] || tree > left_visibility {
                 total_visible += 1;
-                marked[(row_idx * ROW_LEN) + i] = true;
+                marked[(row_idx * width) + i] = true;
 
                 top_visibility[i] = max(top_visibility[i], tree);
                 left_visibility = max(left_visibility, tree);
@@ -40,20 +72,10 @@ pub fn run_part1(input: &str) -> i64 {
         }
     }
 
-    let (_, last_row) = rows.next().unwrap();
-    top_visibility[..ROW_LEN].copy_from_slice(last_row);
-    let mut bottom_visibility = top_visibility;
-
-    for (row_idx, row) in input
-        .as_bytes()
-        .split(|&b| b == b'\n')
-        .rev()
-        .enumerate()
-        .skip(1)
-        .take(ROW_LEN - 2)
-    {
-        let row_idx = ROW_LEN - row_idx - 1;
-        let index = ROW_LEN - 1;
+    let mut bottom_visibility = forest.row(height - 1).to_vec();
+
+    for (row_idx, row) in forest.rows().enumerate().rev().skip(1).take(height - 2) {
+        let index = width - 1;
         let mut right_visibility = row[index];
 
         if row[index] > bottom_visibility[index] {
@@ -66,10 +88,10 @@ pub fn run_part1(input: &str) -> i64 {
             .enumerate()
             .rev()
             .skip(1)
-            .take(ROW_LEN - 2)
+            .take(width - 2)
         {
             if tree > bottom_visibility[i] || tree > right_visibility {
-                if !marked[(row_idx * ROW_LEN) + i] {
+                if !marked[(row_idx * width) + i] {
                     total_visible += 1;
                 }
 
@@ -82,37 +104,43 @@ pub fn run_part1(input: &str) -> i64 {
     total_visible as i64
 }
 
-fn scenic_score(rows: &[&[u8]], (start_x, start_y): (i64, i64)) -> i64 {
-    const OFFSETS: [(i64, i64); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
-    let height = rows[start_y as usize][start_x as usize];
+fn scenic_score(forest: &Grid<u8>, (start_x, start_y): Pos) -> i64 {
+    const OFFSETS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+    let height = forest[(start_x, start_y)];
 
     OFFSETS
         .into_iter()
         .map(|(dx, dy)| {
-            let (mut x, mut y) = (start_x + dx, start_y + dy);
-
-            while let Some(&tree) = rows.get(y as usize).and_then(|row| row.get(x as usize)) {
-                (x, y) = (x + dx, y + dy);
+            let mut distance = 0;
+            let (mut x, mut y) = (start_x, start_y);
+
+            while let Some(&tree) = x
+                .checked_add_signed(dx)
+                .zip(y.checked_add_signed(dy))
+                .and_then(|pos| {
+                    (x, y) = pos;
+                    forest.get(pos)
+                })
+            {
+                distance += 1;
 
                 if tree >= height {
                     break;
                 }
             }
 
-            (x.abs_diff(start_x) + y.abs_diff(start_y) - 1) as i64
+            distance
         })
         .product()
 }
 
 #[aoc(day8, part2)]
-fn run_part2(input: &str) -> i64 {
-    let row_vec = input.as_bytes().split(|&b| b == b'\n').collect::<Vec<_>>();
-    let rows = &row_vec;
-
-    (0..rows.len())
-        .flat_map(|y| (0..rows[y].len()).map(move |x| scenic_score(rows, (x as i64, y as i64))))
+pub fn run_part2(forest: &Grid<u8>) -> i64 {
+    forest
+        .positions()
+        .map(|pos| scenic_score(forest, pos))
         .max()
-        .unwrap()
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -127,11 +155,29 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 21);
+        assert_eq!(run_part1(&parse_trees(TEST_INPUT.as_bytes()).unwrap()), 21);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 8);
+        assert_eq!(run_part2(&parse_trees(TEST_INPUT.as_bytes()).unwrap()), 8);
+    }
+
+    #[test]
+    fn small_forests() {
+        for (input, visible) in [("", 0), ("5", 1), ("35", 2), ("3\n5\n1", 3), ("30\n25", 4)] {
+            let forest = parse_trees(input.as_bytes()).unwrap();
+            assert_eq!(run_part1(&forest), visible, "{input:?}");
+            assert_eq!(run_part2(&forest), 0, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn malformed_forest() {
+        let error = parse_trees(b"303\n2x5").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (2, 2, Some('x')));
+
+        let error = parse_trees(b"303\n25").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (2, 3, None));
     }
 }
@@ -1,20 +1,24 @@
-use std::iter;
+use std::{collections::HashSet, iter};
 
+use crate::{error::ParseError, parse, solution::Solution};
+
+const DAY: u8 = 9;
+
+#[derive(Debug)]
 pub struct Step {
     pub direction: Direction,
-    pub count: u8,
+    pub count: u32,
 }
 
-impl Step {
-    pub fn new(dir: u8, count: u8) -> Step {
-        Self {
-            direction: dir.into(),
-            count,
-        }
+impl TryFrom<&[u8]> for Step {
+    type Error = ParseError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        parse_step(slice)
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 pub enum Direction {
     Left,
     Right,
@@ -23,7 +27,7 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn offset(self, x: &mut i16, y: &mut i16) {
+    pub fn offset(self, x: &mut i32, y: &mut i32) {
         match self {
             Direction::Left => *x -= 1,
             Direction::Right => *x += 1,
@@ -33,52 +37,155 @@ impl Direction {
     }
 }
 
-impl From<u8> for Direction {
-    fn from(shorthand: u8) -> Self {
+impl TryFrom<u8> for Direction {
+    type Error = ParseError;
+
+    fn try_from(shorthand: u8) -> Result<Self, Self::Error> {
         match shorthand {
-            b'L' => Self::Left,
-            b'R' => Self::Right,
-            b'U' => Self::Up,
-            b'D' => Self::Down,
-            _ => unreachable!(),
+            b'L' => Ok(Self::Left),
+            b'R' => Ok(Self::Right),
+            b'U' => Ok(Self::Up),
+            b'D' => Ok(Self::Down),
+            _ => Err(ParseError::unexpected(DAY, &[shorthand], 0, "L, R, U or D")),
         }
     }
 }
 
-pub fn parse_step(slice: &[u8]) -> Step {
-    let (dir, count) = match slice {
-        [dir, b' ', count] => (*dir, count - b'0'),
-        [dir, b' ', count_a, count_b] => (*dir, (count_a - b'0') * 10 + (count_b - b'0')),
-        _ => unreachable!(),
+pub fn parse_step(slice: &[u8]) -> Result<Step, ParseError> {
+    let direction = match slice.first() {
+        Some(&shorthand) => Direction::try_from(shorthand)?,
+        None => return Err(ParseError::unexpected(DAY, slice, 0, "L, R, U or D")),
     };
 
-    Step::new(dir, count)
+    if slice.get(1) != Some(&b' ') {
+        return Err(ParseError::unexpected(DAY, slice, 1, "' '"));
+    }
+
+    let (count, end) = parse::number_at(DAY, slice, 2)?;
+
+    if end != slice.len() {
+        return Err(ParseError::unexpected(DAY, slice, end, "end of line"));
+    }
+
+    Ok(Step { direction, count })
 }
 
-pub fn get_steps(input: &str) -> impl Iterator<Item = Direction> + '_ {
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Rope Bridge");
+
+    type Input = Vec<Step>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_steps(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
+
+#[aoc_generator(day9)]
+pub fn parse_steps(input: &str) -> Result<Vec<Step>, ParseError> {
     input
         .as_bytes()
         .split(|&b| b == b'\n')
-        .filter(|s| !s.is_empty())
-        .map(parse_step)
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, s)| Step::try_from(s).map_err(|e| e.on_line(i)))
+        .collect()
+}
+
+pub fn get_steps(steps: &[Step]) -> impl Iterator<Item = Direction> + '_ {
+    steps
+        .iter()
         .flat_map(|s| iter::once(s.direction).cycle().take(s.count as usize))
 }
 
-// Need an extra bit for the signedness.
-const BITS_NEEDED: u32 = u8::BITS + 1;
-const BIT_MASK: usize = (1 << BITS_NEEDED) - 1;
+/// Largest area the head can roam for [`Visited`] to keep a flag per position.
+const MAX_GRID_CELLS: u64 = 1 << 24;
+
+/// The positions a knot has visited.
+///
+/// The knots never leave the rectangle the head moves in, so that's measured first and gets a flag
+/// per position, unless the head wanders so far that a set of positions is smaller.
+enum Visited {
+    Grid {
+        min_x: i32,
+        min_y: i32,
+        width: usize,
+        cells: Vec<bool>,
+    },
+    Set(HashSet<(i32, i32)>),
+}
+
+impl Visited {
+    fn new(steps: &[Step]) -> Self {
+        let (mut x, mut y) = (0i64, 0i64);
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (0, 0, 0, 0);
 
-#[aoc(day9, part1)]
+        for step in steps {
+            let count = step.count as i64;
 
-pub fn run_part1(input: &str) -> i64 {
-    let (mut head_x, mut head_y) = (0i16, 0i16);
-    let (mut tail_x, mut tail_y) = (0i16, 0i16);
+            match step.direction {
+                Direction::Left => x -= count,
+                Direction::Right => x += count,
+                Direction::Up => y -= count,
+                Direction::Down => y += count,
+            }
+
+            (min_x, max_x) = (min_x.min(x), max_x.max(x));
+            (min_y, max_y) = (min_y.min(y), max_y.max(y));
+        }
+
+        let (width, height) = ((max_x - min_x + 1) as u64, (max_y - min_y + 1) as u64);
+
+        match width.checked_mul(height) {
+            Some(cells) if cells <= MAX_GRID_CELLS => Self::Grid {
+                min_x: min_x as i32,
+                min_y: min_y as i32,
+                width: width as usize,
+                cells: vec![false; cells as usize],
+            },
+            _ => Self::Set(HashSet::new()),
+        }
+    }
+
+    /// Marks `(x, y)` as visited, returning whether it's the first visit.
+    fn insert(&mut self, (x, y): (i32, i32)) -> bool {
+        match self {
+            Self::Grid {
+                min_x,
+                min_y,
+                width,
+                cells,
+            } => {
+                let cell = &mut cells[(y - *min_y) as usize * *width + (x - *min_x) as usize];
+                !std::mem::replace(cell, true)
+            }
+            Self::Set(positions) => positions.insert((x, y)),
+        }
+    }
+}
+
+#[aoc(day9, part1)]
+pub fn run_part1(steps: &[Step]) -> i64 {
+    let (mut head_x, mut head_y) = (0i32, 0i32);
+    let (mut tail_x, mut tail_y) = (0i32, 0i32);
 
     let mut positions = 1;
-    let mut visited = [false; (1 << (BITS_NEEDED * 2 + 1)) - 1];
-    visited[hash_pos(0, 0)] = true;
+    let mut visited = Visited::new(steps);
+    visited.insert((0, 0));
 
-    for dir in get_steps(input) {
+    for dir in get_steps(steps) {
         dir.offset(&mut head_x, &mut head_y);
 
         let Some(new_tail) = follow_pos((tail_x, tail_y), (head_x, head_y)) else {
@@ -87,10 +194,7 @@ pub fn run_part1(input: &str) -> i64 {
 
         (tail_x, tail_y) = new_tail;
 
-        let hash = hash_pos(tail_x, tail_y);
-
-        if !visited[hash] {
-            visited[hash] = true;
+        if visited.insert((tail_x, tail_y)) {
             positions += 1;
         }
     }
@@ -99,14 +203,14 @@ pub fn run_part1(input: &str) -> i64 {
 }
 
 #[aoc(day9, part2)]
-fn run_part2(input: &str) -> i64 {
-    let mut rope = [(0i16, 0i16); 10];
+pub fn run_part2(steps: &[Step]) -> i64 {
+    let mut rope = [(0i32, 0i32); 10];
 
     let mut positions = 1;
-    let mut visited = [false; (1 << (BITS_NEEDED * 2 + 1)) - 1];
-    visited[hash_pos(0, 0)] = true;
+    let mut visited = Visited::new(steps);
+    visited.insert((0, 0));
 
-    for dir in get_steps(input) {
+    for dir in get_steps(steps) {
         dir.offset(&mut rope[0].0, &mut rope[0].1);
 
         for i in 1..10 {
@@ -116,13 +220,8 @@ fn run_part2(input: &str) -> i64 {
 
             rope[i] = new_pos;
 
-            if i == 9 {
-                let hash = hash_pos(rope[9].0, rope[9].1);
-
-                if !visited[hash] {
-                    visited[hash] = true;
-                    positions += 1;
-                }
+            if i == 9 && visited.insert(rope[9]) {
+                positions += 1;
             }
         }
     }
@@ -131,7 +230,7 @@ fn run_part2(input: &str) -> i64 {
 }
 
 #[allow(overlapping_range_endpoints)]
-fn follow_pos((x, y): (i16, i16), (target_x, target_y): (i16, i16)) -> Option<(i16, i16)> {
+fn follow_pos((x, y): (i32, i32), (target_x, target_y): (i32, i32)) -> Option<(i32, i32)> {
     Some(match ((target_x - x) as i8, (target_y - y) as i8) {
         (-1..=1, -1..=1) => return None,
         (-2, 0) => (x - 1, y),
@@ -148,10 +247,6 @@ fn follow_pos((x, y): (i16, i16), (target_x, target_y): (i16, i16)) -> Option<(i
     })
 }
 
-fn hash_pos(x: i16, y: i16) -> usize {
-    ((x + 128) as usize & BIT_MASK) << BITS_NEEDED | ((y + 128) as usize & BIT_MASK)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,16 +271,40 @@ U 20";
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT_A), 13);
+        assert_eq!(run_part1(&parse_steps(TEST_INPUT_A).unwrap()), 13);
     }
 
     #[test]
     fn part2_example_a() {
-        assert_eq!(run_part2(TEST_INPUT_A), 1);
+        assert_eq!(run_part2(&parse_steps(TEST_INPUT_A).unwrap()), 1);
     }
 
     #[test]
     fn part2_example_b() {
-        assert_eq!(run_part2(TEST_INPUT_B), 36);
+        assert_eq!(run_part2(&parse_steps(TEST_INPUT_B).unwrap()), 36);
+    }
+
+    #[test]
+    fn long_moves() {
+        let steps = parse_steps("R 400\nL 800").unwrap();
+
+        assert_eq!(run_part1(&steps), 799);
+        assert_eq!(run_part2(&steps), 783);
+    }
+
+    #[test]
+    fn far_apart_moves() {
+        // Too far apart for a flag per position, so these are kept in a set.
+        let steps = parse_steps("R 100000\nD 100000\nL 3").unwrap();
+
+        assert_eq!(run_part1(&steps), 200_001);
+        assert_eq!(run_part2(&steps), 199_984);
+    }
+
+    #[test]
+    fn malformed_step() {
+        let error = parse_steps("R 4\nU 4\nX 3").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (3, 1, Some('X')));
     }
 }
@@ -1,7 +1,11 @@
-#[derive(Clone, Copy)]
+use crate::{error::ParseError, parse, solution::Solution};
+
+const DAY: u8 = 10;
+
+#[derive(Debug, Clone, Copy)]
 pub enum Opcode {
     Noop,
-    AddX(i8),
+    AddX(i32),
 }
 
 impl Opcode {
@@ -13,35 +17,74 @@ impl Opcode {
     }
 }
 
-fn parse_instruction(instruction: &[u8]) -> Opcode {
-    match &instruction[..4] {
-        b"noop" => Opcode::Noop,
-        b"addx" => {
-            let immediate = match &instruction[5..] {
-                [b'-', num @ b'0'..=b'9'] => -((*num - b'0') as i8),
-                [b'-', a @ b'0'..=b'9', b @ b'0'..=b'9'] => {
-                    -(((*a - b'0') * 10 + (*b - b'0')) as i8)
-                }
-                [num @ b'0'..=b'9'] => (*num - b'0') as i8,
-                [a @ b'0'..=b'9', b @ b'0'..=b'9'] => ((*a - b'0') * 10 + (*b - b'0')) as i8,
-                _ => unreachable!(),
-            };
-
-            Opcode::AddX(immediate)
+impl TryFrom<&[u8]> for Opcode {
+    type Error = ParseError;
+
+    fn try_from(instruction: &[u8]) -> Result<Self, Self::Error> {
+        parse_instruction(instruction)
+    }
+}
+
+fn parse_instruction(instruction: &[u8]) -> Result<Opcode, ParseError> {
+    match instruction.get(..4) {
+        Some(b"noop") if instruction.len() == 4 => Ok(Opcode::Noop),
+        Some(b"noop") => Err(ParseError::unexpected(DAY, instruction, 4, "end of line")),
+        Some(b"addx") => {
+            if instruction.get(4) != Some(&b' ') {
+                return Err(ParseError::unexpected(DAY, instruction, 4, "' '"));
+            }
+
+            let (immediate, end) = parse::number_at(DAY, instruction, 5)?;
+
+            if end != instruction.len() {
+                return Err(ParseError::unexpected(DAY, instruction, end, "end of line"));
+            }
+
+            Ok(Opcode::AddX(immediate))
         }
-        _ => unreachable!(),
+        _ => Err(ParseError::unexpected(DAY, instruction, 0, "noop or addx")),
     }
 }
 
-pub fn cpu_iter(input: &str) -> impl Iterator<Item = i64> + '_ {
-    let mut x = 1;
-    let mut v = None;
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Cathode-Ray Tube");
+
+    type Input = Vec<Opcode>;
+    type Output1 = i64;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
 
-    let mut instructions = input
+#[aoc_generator(day10)]
+pub fn parse_program(input: &str) -> Result<Vec<Opcode>, ParseError> {
+    input
         .as_bytes()
         .split(|&b| b == b'\n')
-        .filter(|s| !s.is_empty())
-        .map(parse_instruction);
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, s)| Opcode::try_from(s).map_err(|e| e.on_line(i)))
+        .collect()
+}
+
+pub fn cpu_iter(program: &[Opcode]) -> impl Iterator<Item = i64> + '_ {
+    let mut x = 1;
+    let mut v = None;
+
+    let mut instructions = program.iter().copied();
 
     std::iter::from_fn(move || {
         if let Some(v) = v.take() {
@@ -60,8 +103,8 @@ pub fn cpu_iter(input: &str) -> impl Iterator<Item = i64> + '_ {
 }
 
 #[aoc(day10, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    cpu_iter(input)
+pub fn run_part1(program: &[Opcode]) -> i64 {
+    cpu_iter(program)
         .enumerate()
         .map(|(i, x)| (i as i64 + 2) * x)
         .skip(18)
@@ -70,10 +113,10 @@ pub fn run_part1(input: &str) -> i64 {
 }
 
 #[aoc(day10, part2)]
-pub fn run_part2(input: &str) -> String {
+pub fn run_part2(program: &[Opcode]) -> String {
     let mut output = [[b'.'; 40]; 6];
     let mut sprite_pos = 1;
-    let mut sprite_positions = cpu_iter(input);
+    let mut sprite_positions = cpu_iter(program);
 
     for row in &mut output {
         for col in 0..40 {
@@ -96,7 +139,6 @@ pub fn run_part2(input: &str) -> String {
     }
 
     string.pop();
-    println!("{string}");
 
     string
 }
@@ -256,13 +298,13 @@ noop"
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 13140);
+        assert_eq!(run_part1(&parse_program(TEST_INPUT).unwrap()), 13140);
     }
 
     #[test]
     fn part2_example() {
         assert_eq!(
-            &run_part2(TEST_INPUT),
+            run_part2(&parse_program(TEST_INPUT).unwrap()),
             "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -271,4 +313,11 @@ noop"
 #######.......#######.......#######....."
         );
     }
+
+    #[test]
+    fn malformed_instruction() {
+        let error = parse_program("noop\naddx 1x").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (2, 7, Some('x')));
+    }
 }
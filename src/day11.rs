@@ -1,10 +1,18 @@
-use std::{cmp::Reverse, mem::MaybeUninit};
+use std::cmp::Reverse;
 
-use regex::bytes::{Captures, Regex};
+use regex::bytes::{Captures, Match, Regex};
 
+use crate::{
+    error::ParseError,
+    parse::{self, Number},
+    solution::Solution,
+};
+
+const DAY: u8 = 11;
+
+#[derive(Debug, Clone)]
 pub struct Monkey {
-    items: [u64; 64],
-    item_count: usize,
+    items: Vec<u64>,
 
     operation: Operation,
 
@@ -17,95 +25,139 @@ pub struct Monkey {
 
 impl Monkey {
     pub fn add_item(&mut self, item: u64) {
-        self.items[self.item_count] = item;
-        self.item_count += 1;
+        self.items.push(item);
     }
 
-    pub fn items(&mut self) -> ([u64; 64], usize) {
-        let items = std::mem::replace(&mut self.items, [0; 64]);
-        let count = std::mem::take(&mut self.item_count);
-
-        self.inspection_count += count;
-        (items, count)
+    /// Swaps the held items into `buffer`, counting them as inspected.
+    pub fn take_items(&mut self, buffer: &mut Vec<u64>) {
+        std::mem::swap(&mut self.items, buffer);
+        self.inspection_count += buffer.len();
     }
 
     pub fn test(&self, value: u64) -> usize {
-        match (value % self.divisor) == 0 {
+        match value.is_multiple_of(self.divisor) {
             true => self.index_true,
             false => self.index_false,
         }
     }
 }
 
-impl<'a> From<Captures<'a>> for Monkey {
-    fn from(cap: Captures<'a>) -> Self {
-        let mut items = [0; 64];
-        let mut item_count = 0;
+impl Monkey {
+    /// Builds a monkey from its regex captures, locating any errors within the whole `input`.
+    fn parse(input: &[u8], cap: &Captures) -> Result<Self, ParseError> {
+        let field = |name| -> Match { cap.name(name).unwrap() };
+        let locate = |field: Match, e: ParseError| {
+            ParseError::at_offset(DAY, input, field.start() + e.column - 1, e.expected)
+        };
+        let parse_int = |name| -> Result<u64, ParseError> {
+            let field = field(name);
+            slice_to_int(field.as_bytes()).map_err(|e| locate(field, e))
+        };
+
+        let items_field = field("items");
+        let mut items = Vec::new();
+        let mut item_start = 0;
+
+        // The pattern takes the rest of the line, so a bad item is reported where it goes wrong.
+        if !items_field.as_bytes().is_empty() {
+            for item in items_field.as_bytes().split(|&b| b == b',') {
+                let value =
+                    slice_to_int(item).map_err(|e| locate(items_field, e.shifted(item_start)))?;
+
+                items.push(value);
+                item_start += item.len() + 1;
+            }
+        }
 
-        for item in cap["items"].split(|&b| b == b',').map(slice_to_int) {
-            items[item_count] = item as _;
-            item_count += 1;
+        let value = field("value");
+
+        let divisor = parse_int("test_param")?;
+        if divisor == 0 {
+            let offset = field("test_param").start();
+            return Err(ParseError::at_offset(
+                DAY,
+                input,
+                offset,
+                "non-zero divisor",
+            ));
         }
 
-        Self {
+        Ok(Self {
             items,
-            item_count,
-            operation: Operation::new(cap["op"][0], &cap["value"]),
-            divisor: slice_to_int(&cap["test_param"]) as _,
-            index_true: slice_to_int(&cap["true_idx"]) as usize,
-            index_false: slice_to_int(&cap["false_idx"]) as usize,
+            operation: Operation::new(cap["op"][0], value.as_bytes())
+                .map_err(|e| locate(value, e))?,
+            divisor,
+            index_true: parse_int("true_idx")? as usize,
+            index_false: parse_int("false_idx")? as usize,
             inspection_count: 0,
-        }
+        })
     }
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum Operation {
-    Add(u8),
-    Multiply(u8),
+    Add(u64),
+    Multiply(u64),
     MultiplySelf,
 }
 
 impl Operation {
-    pub fn new(op: u8, value: &[u8]) -> Self {
+    pub fn new(op: u8, value: &[u8]) -> Result<Self, ParseError> {
         match (op, value) {
-            (b'*', b"old") => Self::MultiplySelf,
-            (b'+', val) => Self::Add(slice_to_int(val)),
-            (b'*', val) => Self::Multiply(slice_to_int(val)),
-            _ => unreachable!(),
+            (b'*', b"old") => Ok(Self::MultiplySelf),
+            (b'+', val) => Ok(Self::Add(slice_to_int(val)?)),
+            (b'*', val) => Ok(Self::Multiply(slice_to_int(val)?)),
+            _ => Err(ParseError::unexpected(DAY, &[op], 0, "'*' or '+'")),
         }
     }
 
-    pub fn apply(&self, val: u64) -> u64 {
+    /// The new worry level, wide enough that no 64-bit level can overflow it.
+    pub fn apply(&self, val: u64) -> u128 {
+        let val = val as u128;
+
         match *self {
-            Operation::Add(arg) => val + (arg as u64),
-            Operation::Multiply(arg) => val * (arg as u64),
+            Operation::Add(arg) => val + arg as u128,
+            Operation::Multiply(arg) => val * arg as u128,
             Operation::MultiplySelf => val * val,
         }
     }
 }
 
-pub fn slice_to_int(slice: &[u8]) -> u8 {
-    match slice.trim_ascii_start() {
-        [count @ b'0'..=b'9'] => count - b'0',
-        [count_a @ b'0'..=b'9', count_b @ b'0'..=b'9'] => (count_a - b'0') * 10 + (count_b - b'0'),
-        _ => unreachable!(),
-    }
+pub fn slice_to_int<T: Number>(slice: &[u8]) -> Result<T, ParseError> {
+    let leading = slice.len() - slice.trim_ascii_start().len();
+
+    parse::exact(&slice[leading..])
+        .map_err(|e| ParseError::unexpected(DAY, slice, leading + e.index(), e.expected()))
 }
 
-#[cfg(not(test))]
-const MONKEY_COUNT: usize = 8;
-#[cfg(test)]
-const MONKEY_COUNT: usize = 4;
+pub struct Day11;
 
-#[cfg(not(test))]
-const LEAST_COMMON_PRIME_MULTIPLE: u64 = 9699690;
-#[cfg(test)]
-const LEAST_COMMON_PRIME_MULTIPLE: u64 = 96577;
+impl Solution for Day11 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Monkey in the Middle");
+
+    type Input = Vec<Monkey>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
 
-pub fn parse_monkeys(input: &str) -> [Monkey; MONKEY_COUNT] {
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
+
+#[aoc_generator(day11)]
+pub fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
     let regex = Regex::new(
-        r"(?m)^Monkey [0-9]:
-  Starting items: (?P<items>(?:[0-9]{1,2}(?:, )?)+)
+        r"(?m)^Monkey [0-9]+:
+  Starting items: (?P<items>[^\n]*)
   Operation: new = old (?P<op>[*+]) (?P<value>.+)
   Test: divisible by (?P<test_param>[0-9]+)
     If true: throw to monkey (?P<true_idx>[0-9]+)
@@ -113,43 +165,116 @@ pub fn parse_monkeys(input: &str) -> [Monkey; MONKEY_COUNT] {
     )
     .unwrap();
 
-    let mut monkeys: [MaybeUninit<Monkey>; MONKEY_COUNT] =
-        unsafe { MaybeUninit::uninit().assume_init() };
+    let input = input.as_bytes();
+    let mut monkeys = Vec::new();
+    let mut targets = Vec::new();
+    let mut modulus = 1;
+    let mut expected_start = 0;
 
-    for (i, monkey) in regex
-        .captures_iter(input.as_bytes())
-        .map(Monkey::from)
-        .enumerate()
+    // The regex skips over malformed monkeys, so make sure nothing was left unmatched.
+    let check_skipped = |start: usize, end: usize| match input[start..end]
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
     {
-        monkeys[i].write(monkey);
+        Some(i) => Err(ParseError::at_offset(
+            DAY,
+            input,
+            start + i,
+            "monkey definition",
+        )),
+        None => Ok(()),
+    };
+
+    for cap in regex.captures_iter(input) {
+        let whole = cap.get(0).unwrap();
+        check_skipped(expected_start, whole.start())?;
+
+        let monkey = Monkey::parse(input, &cap)?;
+
+        modulus = lcm(modulus, monkey.divisor).ok_or_else(|| {
+            ParseError::at_offset(
+                DAY,
+                input,
+                cap.name("test_param").unwrap().start(),
+                "divisor keeping the worry modulus within 64 bits",
+            )
+        })?;
+
+        targets.push((monkey.index_true, cap.name("true_idx").unwrap().start()));
+        targets.push((monkey.index_false, cap.name("false_idx").unwrap().start()));
+        monkeys.push(monkey);
+
+        expected_start = whole.end();
     }
 
-    unsafe { std::mem::transmute(monkeys) }
+    check_skipped(expected_start, input.len())?;
+
+    if monkeys.len() < 2 {
+        return Err(ParseError::at_offset(
+            DAY,
+            input,
+            input.len(),
+            "at least two monkeys",
+        ));
+    }
+
+    if let Some(&(_, offset)) = targets.iter().find(|(index, _)| *index >= monkeys.len()) {
+        return Err(ParseError::at_offset(
+            DAY,
+            input,
+            offset,
+            "index of an existing monkey",
+        ));
+    }
+
+    Ok(monkeys)
+}
+
+/// The smallest modulus that preserves every monkey's divisibility test.
+pub fn worry_modulus(monkeys: &[Monkey]) -> u64 {
+    monkeys
+        .iter()
+        .try_fold(1, |modulus, m| lcm(modulus, m.divisor))
+        .expect("parse_monkeys keeps the modulus within 64 bits")
+}
+
+/// The least common multiple of `a` and `b`, or `None` if it doesn't fit in a `u64`.
+fn lcm(a: u64, b: u64) -> Option<u64> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
 }
 
 #[aoc(day11, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    monkey_rounds::<20, 3>(parse_monkeys(input)) as i64
+pub fn run_part1(monkeys: &[Monkey]) -> i64 {
+    monkey_rounds::<20, 3>(monkeys.to_vec()) as i64
 }
 
 #[aoc(day11, part2)]
-pub fn run_part2(input: &str) -> i64 {
-    monkey_rounds::<10_000, 1>(parse_monkeys(input)) as i64
+pub fn run_part2(monkeys: &[Monkey]) -> i64 {
+    monkey_rounds::<10_000, 1>(monkeys.to_vec()) as i64
 }
 
-pub fn monkey_rounds<const ROUNDS: usize, const WORRY_DIV: u64>(
-    mut monkeys: [Monkey; MONKEY_COUNT],
-) -> usize {
+pub fn monkey_rounds<const ROUNDS: usize, const WORRY_DIV: u64>(mut monkeys: Vec<Monkey>) -> usize {
+    // Dividing by the relief factor only keeps the tests intact if it is part of the modulus too.
+    let modulus = worry_modulus(&monkeys) as u128 * WORRY_DIV as u128;
+    let mut items = Vec::new();
+
     for _ in 0..ROUNDS {
-        for i in 0..MONKEY_COUNT {
-            let (mut items, count) = monkeys[i].items();
+        for i in 0..monkeys.len() {
+            monkeys[i].take_items(&mut items);
 
-            for item in &mut items[..count] {
-                *item = monkeys[i].operation.apply(*item) % LEAST_COMMON_PRIME_MULTIPLE;
-                *item /= WORRY_DIV;
+            for item in items.drain(..) {
+                let item = (monkeys[i].operation.apply(item) % modulus / WORRY_DIV as u128) as u64;
 
-                let next_monkey = monkeys[i].test(*item);
-                monkeys[next_monkey].add_item(*item);
+                let next_monkey = monkeys[i].test(item);
+                monkeys[next_monkey].add_item(item);
             }
         }
     }
@@ -192,11 +317,80 @@ Monkey 3:
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 10605);
+        assert_eq!(run_part1(&parse_monkeys(TEST_INPUT).unwrap()), 10605);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 2713310158);
+        assert_eq!(run_part2(&parse_monkeys(TEST_INPUT).unwrap()), 2713310158);
+    }
+
+    #[test]
+    fn modulus_from_divisors() {
+        let monkeys = parse_monkeys(TEST_INPUT).unwrap();
+
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(worry_modulus(&monkeys), 23 * 19 * 13 * 17);
+    }
+
+    #[test]
+    fn modulus_beyond_32_bits() {
+        let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * old
+  Test: divisible by 2147483647
+    If true: throw to monkey 1
+    If false: throw to monkey 2
+
+Monkey 1:
+  Starting items: 54, 65
+  Operation: new = old + 4294967000
+  Test: divisible by 2147483629
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 11890
+  Operation: new = old * 1000003
+  Test: divisible by 3
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+        let monkeys = parse_monkeys(input).unwrap();
+
+        assert_eq!(run_part1(&monkeys), 3306);
+        assert_eq!(run_part2(&monkeys), 2499250056);
+
+        let input = input.replace("2147483629", "4294967279");
+        let error = parse_monkeys(&input).unwrap_err();
+
+        assert_eq!((error.line, error.column), (18, 22));
+    }
+
+    #[test]
+    fn unplayable_monkeys() {
+        let input = TEST_INPUT.replacen("divisible by 19", "divisible by 0", 1);
+        let error = parse_monkeys(&input).unwrap_err();
+
+        assert_eq!((error.line, error.column), (11, 22));
+
+        let (first, _) = TEST_INPUT.split_once("\n\n").unwrap();
+        let error = parse_monkeys(first).unwrap_err();
+
+        assert_eq!((error.line, error.column), (6, 32));
+        assert_eq!(parse_monkeys("").unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn malformed_item() {
+        let input = TEST_INPUT.replacen("54, 65", "54, 6x", 1);
+        let error = parse_monkeys(&input).unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (9, 24, Some('x')));
+
+        let input = TEST_INPUT.replacen("old + 6", "old + 6y", 1);
+        let error = parse_monkeys(&input).unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (10, 27, Some('y')));
     }
 }
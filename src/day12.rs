@@ -1,170 +1,128 @@
-#[cfg(not(test))]
-const WIDTH: usize = 167;
-#[cfg(test)]
-const WIDTH: usize = 8;
-
-#[cfg(not(test))]
-const HEIGHT: usize = 41;
-#[cfg(test)]
-const HEIGHT: usize = 5;
-
-static NEIGHBORS: [([usize; 4], usize); WIDTH * HEIGHT] = {
-    const RIGHT: usize = WIDTH - 1;
-    const BOTTOM: usize = HEIGHT - 1;
-
-    let mut neighbors = [([0; 4], 0); WIDTH * HEIGHT];
-
-    let mut row = 0;
-
-    while row < HEIGHT {
-        let mut col = 0;
-
-        while col < WIDTH {
-            let data = match (row, col) {
-                (0, 0) => ([1, WIDTH, 0, 0], 2),
-                (BOTTOM, 0) => ([shift(BOTTOM - 1, 0), shift(BOTTOM, 1), 0, 0], 2),
-                (0, RIGHT) => ([shift(0, RIGHT - 1), shift(1, RIGHT), 0, 0], 2),
-                (BOTTOM, RIGHT) => (
-                    [shift(BOTTOM - 1, RIGHT), shift(BOTTOM, RIGHT - 1), 0, 0],
-                    2,
-                ),
-
-                (0, y) => ([y - 1, y + 1, WIDTH + y, 0], 3),
-                (BOTTOM, y) => (
-                    [
-                        shift(BOTTOM, y - 1),
-                        shift(BOTTOM, y + 1),
-                        shift(BOTTOM - 1, y),
-                        0,
-                    ],
-                    3,
-                ),
-                (x, 0) => ([shift(x - 1, 0), shift(x + 1, 0), shift(x, 1), 0], 3),
-                (x, RIGHT) => (
-                    [
-                        shift(x - 1, RIGHT),
-                        shift(x + 1, RIGHT),
-                        shift(x, RIGHT - 1),
-                        0,
-                    ],
-                    3,
-                ),
-
-                (x, y) => (
-                    [
-                        shift(x - 1, y),
-                        shift(x + 1, y),
-                        shift(x, y - 1),
-                        shift(x, y + 1),
-                    ],
-                    4,
-                ),
-            };
-
-            neighbors[shift(row, col)] = data;
-            col += 1;
-        }
-
-        row += 1;
-    }
+use tinyvec::ArrayVec;
 
-    neighbors
+use crate::{
+    error::ParseError,
+    grid::{Grid, Pos},
+    solution::Solution,
 };
 
-static NEIGHBOR_SLICES: [&[usize]; WIDTH * HEIGHT] = {
-    let mut neighbors = [&[] as &[usize]; WIDTH * HEIGHT];
+const DAY: u8 = 12;
 
-    let mut row = 0;
+type Neighbours = ArrayVec<[Pos; 4]>;
 
-    while row < HEIGHT {
-        let mut col = 0;
+/// A heightmap along with the moves out of each cell, computed once per input.
+#[derive(Debug)]
+pub struct Heightmap {
+    heights: Grid<u8>,
+    start: Pos,
+    goal: Pos,
+    climbs: Grid<Neighbours>,
+    descents: Grid<Neighbours>,
+}
 
-        while col < WIDTH {
-            let index = shift(row, col);
-            let (arr, len) = &NEIGHBORS[index];
-            let slice = unsafe { std::slice::from_raw_parts(arr.as_ptr(), *len) };
-            neighbors[shift(row, col)] = slice;
-            col += 1;
+impl Heightmap {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let input = input.as_bytes();
+        let mut heights = Grid::parse(
+            DAY,
+            input,
+            |b| matches!(b, b'a'..=b'z' | b'S' | b'E'),
+            "height, S or E",
+        )?;
+
+        let find = |marker, expected| {
+            let index = heights.cells().iter().position(|&b| b == marker);
+            index
+                .map(|i| heights.pos_of(i))
+                .ok_or_else(|| ParseError::at_offset(DAY, input, input.len(), expected))
+        };
+
+        let start = find(b'S', "start marker S")?;
+        let goal = find(b'E', "goal marker E")?;
+
+        heights[start] = b'a';
+        heights[goal] = b'z';
+
+        let mut climbs = Grid::filled(heights.width(), heights.height(), Neighbours::new());
+        let mut descents = climbs.clone();
+
+        for pos in heights.positions() {
+            for n in heights.neighbours4(pos) {
+                if heights[n] <= heights[pos] + 1 {
+                    climbs[pos].push(n);
+                    descents[n].push(pos);
+                }
+            }
         }
 
-        row += 1;
+        Ok(Self {
+            heights,
+            start,
+            goal,
+            climbs,
+            descents,
+        })
     }
 
-    neighbors
-};
+    pub fn width(&self) -> usize {
+        self.heights.width()
+    }
 
-const fn shift(row: usize, col: usize) -> usize {
-    row * WIDTH + col
+    pub fn height(&self) -> usize {
+        self.heights.height()
+    }
 }
 
-#[aoc(day12, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    let input = input.as_bytes();
-    let mut map = [0u8; WIDTH * HEIGHT];
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Hill Climbing Algorithm");
+
+    type Input = Heightmap;
+    type Output1 = Result<i64, &'static str>;
+    type Output2 = Result<i64, &'static str>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_heightmap(input)
+    }
 
-    for row in 0..HEIGHT {
-        let start = row * WIDTH;
-        map[start..(start + WIDTH)].copy_from_slice(&input[(start + row)..(start + row + WIDTH)]);
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
     }
 
-    let starting_pos = map.iter().position(|&b| b == b'S').unwrap();
-    let goal_pos = map.iter().position(|&b| b == b'E').unwrap();
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
 
-    map[starting_pos] = b'a';
-    map[goal_pos] = b'z';
+#[aoc_generator(day12)]
+pub fn parse_heightmap(input: &str) -> Result<Heightmap, ParseError> {
+    Heightmap::new(input)
+}
 
+#[aoc(day12, part1)]
+pub fn run_part1(map: &Heightmap) -> Result<i64, &'static str> {
     let path = pathfinding::prelude::bfs(
-        &starting_pos,
-        |&p| {
-            NEIGHBOR_SLICES[p]
-                .iter()
-                .filter(move |&&n| (0..=map[p] + 1).contains(&map[n]))
-                .copied()
-        },
-        |&p| p == goal_pos,
+        &map.start,
+        |&p| map.climbs[p].iter().copied(),
+        |&p| p == map.goal,
     )
-    .unwrap();
+    .ok_or("no route from S to E")?;
 
-    path.len() as i64 - 1
+    Ok(path.len() as i64 - 1)
 }
 
 #[aoc(day12, part2)]
-pub fn run_part2(input: &str) -> i64 {
-    let input = input.as_bytes();
-    let mut map = [0u8; WIDTH * HEIGHT];
-
-    for row in 0..HEIGHT {
-        let start = row * WIDTH;
-        map[start..(start + WIDTH)].copy_from_slice(&input[(start + row)..(start + row + WIDTH)]);
-    }
-
-    let mut starting_positions = Vec::new();
-
-    for (i, val) in map
-        .iter_mut()
-        .enumerate()
-        .filter(|(_i, el)| matches!(el, b'a' | b'S'))
-    {
-        *val = b'a';
-        starting_positions.push(i);
-    }
+pub fn run_part2(map: &Heightmap) -> Result<i64, &'static str> {
+    let path = pathfinding::prelude::bfs(
+        &map.goal,
+        |&p| map.descents[p].iter().copied(),
+        |&p| map.heights[p] == b'a',
+    )
+    .ok_or("no route to E from any square of height a")?;
 
-    let goal_pos = map.iter().position(|&b| b == b'E').unwrap();
-    map[goal_pos] = b'z';
-
-    let (paths, optimal) = pathfinding::prelude::dijkstra_partial(
-        &goal_pos,
-        |&p| {
-            NEIGHBOR_SLICES[p]
-                .iter()
-                .filter(move |&&n| ((map[p] - 1)..).contains(&map[n]))
-                .copied()
-                .map(|n| (n, 1))
-        },
-        |p| starting_positions.contains(p),
-    );
-
-    paths[&optimal.unwrap()].1 as i64
+    Ok(path.len() as i64 - 1)
 }
 
 #[cfg(test)]
@@ -179,11 +137,45 @@ abdefghi";
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 31);
+        assert_eq!(run_part1(&parse_heightmap(TEST_INPUT).unwrap()), Ok(31));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 29);
+        assert_eq!(run_part2(&parse_heightmap(TEST_INPUT).unwrap()), Ok(29));
+    }
+
+    #[test]
+    fn detects_map_size() {
+        static SPIRAL: &str = "Sbcdefghijklm
+Eyxwvutsrqpon";
+
+        let map = Heightmap::new(SPIRAL).unwrap();
+
+        assert_eq!((map.width(), map.height()), (13, 2));
+        assert_eq!(run_part1(&map), Ok(25));
+        assert_eq!(run_part2(&map), Ok(25));
+    }
+
+    #[test]
+    fn no_route() {
+        for input in ["SE", "Sz\nzE"] {
+            let map = Heightmap::new(input).unwrap();
+
+            assert!(run_part1(&map).is_err(), "{input:?}");
+            assert!(run_part2(&map).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn malformed_map() {
+        let error = Heightmap::new("Sab\naEc\nab").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (3, 3, None));
+
+        let error = Heightmap::new("Sab\nabc").unwrap_err();
+        assert_eq!(
+            (error.expected, error.line, error.column),
+            ("goal marker E", 2, 4)
+        );
     }
 }
@@ -1,29 +1,38 @@
 use std::cmp::Ordering;
 
+use itertools::Itertools;
 use tinyvec::ArrayVec;
 
+use crate::{error::ParseError, parse, solution::Solution};
+
+const DAY: u8 = 13;
+
+/// Most items a list can hold.
+const MAX_ITEMS: usize = 16;
+
 #[derive(Debug, Clone)]
 pub enum Value {
     Int(u8),
-    List(ArrayVec<[u16; 16]>),
+    List(ArrayVec<[u32; MAX_ITEMS]>),
 }
 
+#[derive(Debug, Clone)]
 pub struct Values {
     values: Vec<Value>,
 }
 
 impl Values {
-    pub fn add_value(&mut self, value: Value) -> u16 {
+    pub fn add_value(&mut self, value: Value) -> u32 {
         let len = self.values.len();
         self.values.push(value);
-        len as u16
+        len as u32
     }
 
     pub fn clear(&mut self) {
         self.values.clear();
     }
 
-    pub fn cmp_indices(&self, left: u16, right: u16) -> Ordering {
+    pub fn cmp_indices(&self, left: u32, right: u32) -> Ordering {
         self.cmp(&self.values[left as usize], &self.values[right as usize])
     }
 
@@ -37,8 +46,9 @@ impl Values {
                 if r.is_empty() {
                     Ordering::Greater
                 } else {
+                    // Compared as `[left]`, which only ties a list of one equal item.
                     match self.cmp(left, &self.values[r[0] as usize]) {
-                        Ordering::Equal => Ordering::Less,
+                        Ordering::Equal if r.len() > 1 => Ordering::Less,
                         ord => ord,
                     }
                 }
@@ -48,7 +58,7 @@ impl Values {
                     Ordering::Less
                 } else {
                     match self.cmp(&self.values[l[0] as usize], right) {
-                        Ordering::Equal => Ordering::Greater,
+                        Ordering::Equal if l.len() > 1 => Ordering::Greater,
                         ord => ord,
                     }
                 }
@@ -56,43 +66,74 @@ impl Values {
         }
     }
 
-    pub fn get_children<'a>(&'a self, indices: &'a [u16]) -> impl Iterator<Item = &Value> + 'a {
+    pub fn get_children<'a>(&'a self, indices: &'a [u32]) -> impl Iterator<Item = &'a Value> + 'a {
         indices.iter().map(|i| &self.values[*i as usize])
     }
 }
 
-#[aoc(day13, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    let mut values = Values {
-        values: Vec::with_capacity(8192),
-    };
+/// Every packet in the input, stored as indices into their shared values.
+#[derive(Debug)]
+pub struct Packets {
+    values: Values,
+    packets: Vec<u32>,
+}
 
-    get_pairs(input)
-        .map(|(left, right)| {
-            let left = process_packet(left.as_bytes(), &mut values);
-            let right = process_packet(right.as_bytes(), &mut values);
+pub struct Day13;
 
-            values.cmp_indices(left, right)
-        })
-        .enumerate()
-        .filter_map(|(i, cmp)| cmp.is_lt().then_some(i + 1))
-        .sum::<usize>() as i64
+impl Solution for Day13 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Distress Signal");
+
+    type Input = Packets;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_packets(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
 }
 
-#[aoc(day13, part2)]
-pub fn run_part2(input: &str) -> i64 {
+#[aoc_generator(day13)]
+pub fn parse_packets(input: &str) -> Result<Packets, ParseError> {
     let mut values = Values {
         values: Vec::with_capacity(8192),
     };
 
-    let mut packet_indices = input
-        .as_bytes()
-        .split(|&b| b == b'\n')
-        .filter(|s| !s.is_empty())
-        .map(|packet| process_packet(packet, &mut values))
-        .collect::<Vec<_>>();
+    let packets = get_packets(input)
+        .map(|(line, packet)| process_packet(packet, &mut values).map_err(|e| e.on_line(line)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Packets { values, packets })
+}
+
+#[aoc(day13, part1)]
+pub fn run_part1(Packets { values, packets }: &Packets) -> i64 {
+    let mut sum = 0;
+
+    for (i, (&left, &right)) in packets.iter().tuples().enumerate() {
+        if values.cmp_indices(left, right).is_lt() {
+            sum += i + 1;
+        }
+    }
+
+    sum as i64
+}
+
+#[aoc(day13, part2)]
+pub fn run_part2(Packets { values, packets }: &Packets) -> i64 {
+    let mut values = values.clone();
+    let mut packet_indices = packets.clone();
 
-    let decoder_keys = [b"[[2]]", b"[[6]]"].map(|divider| process_packet(divider, &mut values));
+    let decoder_keys =
+        [b"[[2]]", b"[[6]]"].map(|divider| process_packet(divider, &mut values).unwrap());
     packet_indices.extend(&decoder_keys);
 
     packet_indices.sort_unstable_by(|&left, &right| values.cmp_indices(left, right));
@@ -104,17 +145,20 @@ pub fn run_part2(input: &str) -> i64 {
         .product::<usize>() as i64
 }
 
-fn get_pairs(input: &str) -> impl Iterator<Item = (&str, &str)> + '_ {
+/// The non-empty lines of the input, along with their line index.
+fn get_packets(input: &str) -> impl Iterator<Item = (usize, &[u8])> + '_ {
     input
-        .split("\n\n")
-        .map(|pair| pair.trim().split_once('\n').unwrap())
+        .as_bytes()
+        .split(|&b| b == b'\n')
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
 }
 
-fn process_packet(packet: &[u8], values: &mut Values) -> u16 {
-    if packet.is_empty() {
-        return values.add_value(Value::List(ArrayVec::new()));
+fn process_packet(packet: &[u8], values: &mut Values) -> Result<u32, ParseError> {
+    if packet.is_empty() || packet == b"[]" {
+        return Ok(values.add_value(Value::List(ArrayVec::new())));
     } else if packet[0] != b'[' {
-        return values.add_value(Value::Int(parse_int(packet)));
+        return Ok(values.add_value(Value::Int(parse_int(packet)?)));
     }
 
     let mut open_brackets: u8 = 0;
@@ -123,31 +167,66 @@ fn process_packet(packet: &[u8], values: &mut Values) -> u16 {
 
     for (i, byte) in packet.iter().enumerate() {
         match byte {
-            b'[' => open_brackets += 1,
-            b']' => open_brackets -= 1,
+            b'[' => {
+                open_brackets = open_brackets.checked_add(1).ok_or_else(|| {
+                    ParseError::unexpected(DAY, packet, i, "at most 255 nested lists")
+                })?;
+            }
+            b']' => {
+                if open_brackets == 0 {
+                    return Err(ParseError::unexpected(DAY, packet, i, "'[' before ']'"));
+                }
+
+                open_brackets -= 1;
+
+                if open_brackets == 0 && i != packet.len() - 1 {
+                    return Err(ParseError::unexpected(DAY, packet, i + 1, "end of packet"));
+                }
+            }
             b',' if open_brackets == 1 => {
-                list.push(process_packet(&packet[item_start + 1..i], values));
+                let item = process_packet(&packet[item_start + 1..i], values)
+                    .map_err(|e| e.shifted(item_start + 1))?;
+
+                push_item(&mut list, item, packet, item_start + 1)?;
                 item_start = i;
             }
             _ => (),
         }
     }
-    list.push(process_packet(
-        &packet[item_start + 1..packet.len() - 1],
-        values,
-    ));
 
-    values.add_value(Value::List(list))
+    if open_brackets != 0 {
+        return Err(ParseError::unexpected(DAY, packet, packet.len(), "']'"));
+    }
+
+    let item = process_packet(&packet[item_start + 1..packet.len() - 1], values)
+        .map_err(|e| e.shifted(item_start + 1))?;
+    push_item(&mut list, item, packet, item_start + 1)?;
+
+    Ok(values.add_value(Value::List(list)))
 }
 
-pub fn parse_int(slice: &[u8]) -> u8 {
-    match slice {
-        [x] => x - b'0',
-        [x, y] => (x - b'0') * 10 + (y - b'0'),
-        _ => unreachable!(),
+/// Adds the item starting at `index` of `packet` to `list`, unless it's full.
+fn push_item(
+    list: &mut ArrayVec<[u32; MAX_ITEMS]>,
+    item: u32,
+    packet: &[u8],
+    index: usize,
+) -> Result<(), ParseError> {
+    match list.try_push(item) {
+        None => Ok(()),
+        Some(_) => Err(ParseError::unexpected(
+            DAY,
+            packet,
+            index,
+            "at most 16 items",
+        )),
     }
 }
 
+pub fn parse_int(slice: &[u8]) -> Result<u8, ParseError> {
+    parse::exact(slice).map_err(|e| e.into_parse_error(DAY, slice))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,11 +257,44 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 13);
+        assert_eq!(run_part1(&parse_packets(TEST_INPUT).unwrap()), 13);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 140);
+        assert_eq!(run_part2(&parse_packets(TEST_INPUT).unwrap()), 140);
+    }
+
+    #[test]
+    fn many_packets() {
+        // More values than 16-bit indices can tell apart.
+        let pair = "[[1,[2,3]],4]\n[[1,[2,4]],3]";
+        let input = vec![pair; 20_000].join("\n\n");
+        let packets = parse_packets(&input).unwrap();
+
+        assert_eq!(run_part1(&packets), (1..=20_000).sum::<i64>());
+        assert_eq!(run_part2(&packets), 40_001 * 40_002);
+    }
+
+    #[test]
+    fn oversized_packets() {
+        let long = format!("[{}]", vec!["1"; 17].join(","));
+        let deep = format!("{}{}", "[".repeat(256), "]".repeat(256));
+
+        let long = parse_packets(&long).unwrap_err();
+        let deep = parse_packets(&deep).unwrap_err();
+
+        assert_eq!((long.column, long.expected), (34, "at most 16 items"));
+        assert_eq!(
+            (deep.column, deep.expected),
+            (256, "at most 255 nested lists")
+        );
+    }
+
+    #[test]
+    fn malformed_packet() {
+        let error = parse_packets("[1,1,3,1,1]\n[1,[1,a],1]").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (2, 7, Some('a')));
     }
 }
@@ -5,16 +5,31 @@ use std::{
 
 use nom::{
     bytes::complete::tag,
-    combinator::{all_consuming, map},
+    combinator::{all_consuming, cut, map, verify},
+    error::ErrorKind,
     multi::separated_list1,
     sequence::separated_pair,
     Finish, IResult,
 };
 
+use crate::{
+    error::ParseError,
+    grid::{Grid, Pos},
+    solution::Solution,
+};
+
+const DAY: u8 = 14;
+
+/// The furthest right a rock may be, which keeps the cave's width in check.
+const MAX_X: i32 = 100_000;
+
+/// The deepest a rock may be, which keeps the cave's height and the pile in part 2 in check.
+const MAX_DEPTH: i32 = 10_000;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 struct Vertex {
-    x: i16,
-    y: i16,
+    x: i32,
+    y: i32,
 }
 
 impl Vertex {
@@ -41,16 +56,16 @@ impl Vertex {
         }
     }
 
-    fn to_index(self, bounds: &Rect) -> usize {
+    fn to_pos(self, bounds: &Rect) -> Pos {
         let x = self.x - bounds.left;
-        let y = self.y - bounds.top as i16;
+        let y = self.y - bounds.top;
 
-        (y as usize * bounds.width()) + x as usize
+        (x as usize, y as usize)
     }
 }
 
-impl AddAssign<[i16; 2]> for Vertex {
-    fn add_assign(&mut self, [dx, dy]: [i16; 2]) {
+impl AddAssign<[i32; 2]> for Vertex {
+    fn add_assign(&mut self, [dx, dy]: [i32; 2]) {
         self.x = self.x + dx;
         self.y = self.y + dy;
     }
@@ -59,7 +74,7 @@ impl AddAssign<[i16; 2]> for Vertex {
 struct VertexIter {
     current: Vertex,
     to: Vertex,
-    direction: [i16; 2],
+    direction: [i32; 2],
     started: bool,
     finished: bool,
 }
@@ -83,8 +98,9 @@ impl Iterator for VertexIter {
     }
 }
 
+/// A path of rock, as the corners it passes through.
 #[derive(Debug)]
-struct Polygon(Vec<Vertex>);
+pub struct Polygon(Vec<Vertex>);
 
 impl Polygon {
     fn bounds(&self) -> Rect {
@@ -100,18 +116,18 @@ impl Polygon {
 
 #[derive(Debug, Copy, Clone)]
 struct Rect {
-    left: i16,
-    right: i16,
-    top: u16,
-    bottom: u16,
+    left: i32,
+    right: i32,
+    top: i32,
+    bottom: i32,
 }
 
 impl Rect {
     fn extend_to_point(&mut self, point: Vertex) {
         self.left = min(self.left, point.x);
         self.right = max(self.right, point.x);
-        self.top = min(self.top, point.y as u16);
-        self.bottom = max(self.bottom, point.y as u16);
+        self.top = min(self.top, point.y);
+        self.bottom = max(self.bottom, point.y);
     }
 
     fn extend_from_rect(&mut self, rect: Rect) {
@@ -128,10 +144,6 @@ impl Rect {
     fn height(self) -> usize {
         (self.bottom - self.top) as usize + 1
     }
-
-    fn area(self) -> usize {
-        self.width() * self.height()
-    }
 }
 
 impl FromIterator<Vertex> for Rect {
@@ -169,17 +181,21 @@ impl Extend<Vertex> for Rect {
 impl Default for Rect {
     fn default() -> Self {
         Self {
-            top: u16::MAX,
-            bottom: 0,
-            left: i16::MAX,
-            right: 0,
+            top: i32::MAX,
+            bottom: i32::MIN,
+            left: i32::MAX,
+            right: i32::MIN,
         }
     }
 }
 
 fn parse_vertex(i: &[u8]) -> IResult<&[u8], Vertex> {
-    let pair = separated_pair(parse_number, tag(b","), parse_number);
-    let to_vertex = |(x, y): (i16, i16)| Vertex { x, y };
+    let pair = separated_pair(
+        parse_coordinate(MAX_X),
+        tag(b","),
+        parse_coordinate(MAX_DEPTH),
+    );
+    let to_vertex = |(x, y): (i32, i32)| Vertex { x, y };
 
     map(pair, to_vertex)(i)
 }
@@ -188,139 +204,152 @@ fn parse_arrow(i: &[u8]) -> IResult<&[u8], ()> {
     map(tag(" -> "), drop)(i)
 }
 
-fn parse_number(i: &[u8]) -> IResult<&[u8], i16> {
-    map(nom::character::complete::i16, |n| n as _)(i)
+fn parse_number(i: &[u8]) -> IResult<&[u8], i32> {
+    nom::character::complete::i32(i)
+}
+
+fn parse_coordinate<'a>(max: i32) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], i32> {
+    verify(parse_number, move |n| (0..=max).contains(n))
 }
 
+/// Parses a path whose segments are all level or upright.
 fn parse_vertex_list(i: &[u8]) -> IResult<&[u8], Polygon> {
-    let vertices = separated_list1(parse_arrow, parse_vertex);
-    map(vertices, Polygon)(i)
+    let (mut i, first) = cut(parse_vertex)(i)?;
+    let mut vertices = vec![first];
+
+    while let Ok((rest, ())) = parse_arrow(i) {
+        let last = vertices[vertices.len() - 1];
+        let in_line = |v: &Vertex| v.x == last.x || v.y == last.y;
+        let (rest, vertex) = cut(verify(parse_vertex, in_line))(rest)?;
+
+        vertices.push(vertex);
+        i = rest;
+    }
+
+    Ok((i, Polygon(vertices)))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Regolith Reservoir");
+
+    type Input = Vec<Polygon>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_polygons(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
 }
 
-fn parse_polygons(i: &[u8]) -> Vec<Polygon> {
-    all_consuming(separated_list1(tag("\n"), parse_vertex_list))(i)
+/// Reads the rock paths, which must lie in `0..=100_000` across and `0..=10_000` down, with every
+/// segment level or upright.
+#[aoc_generator(day14)]
+pub fn parse_polygons(i: &[u8]) -> Result<Vec<Polygon>, ParseError> {
+    all_consuming(separated_list1(tag("\n"), cut(parse_vertex_list)))(i)
         .finish()
         .map(|(_, v)| v)
-        .unwrap()
+        .map_err(|e| {
+            let expected = match e.code {
+                ErrorKind::Verify => "corner in the cave, in line with the previous one",
+                _ => "rock path",
+            };
+
+            ParseError::at_offset(DAY, i, i.len() - e.input.len(), expected)
+        })
 }
 
 #[aoc(day14, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    let polygons = parse_polygons(input.as_bytes());
-
+pub fn run_part1(polygons: &[Polygon]) -> i64 {
     let mut bounds: Rect = polygons.iter().map(|p| p.bounds()).collect();
-    bounds.top = 0;
 
-    let mut board = vec![false; bounds.area()];
+    // The source has to be on the board, even when every rock is off to one side of it.
+    bounds.extend_to_point(Vertex { x: 500, y: 0 });
+
+    let mut board = Grid::filled(bounds.width(), bounds.height(), false);
 
     for point in polygons.iter().flat_map(|p| p.points()) {
-        board[point.to_index(&bounds)] = true;
+        board[point.to_pos(&bounds)] = true;
     }
 
+    let start = Vertex { x: 500, y: 0 }.to_pos(&bounds);
     let mut sand_blocks = 0;
 
-    while simulate_sand_void(&mut board, &bounds) {
-        sand_blocks += 1;
+    // Each grain follows the last one's path until that's blocked, so it carries on from there.
+    let mut path = vec![start];
+
+    while let Some(&sand_pos) = path.last() {
+        match next_sand_pos(&board, sand_pos) {
+            Some(Some(pos)) if board.contains(pos) => path.push(pos),
+            Some(_) => break,
+            None => {
+                board[sand_pos] = true;
+                sand_blocks += 1;
+                path.pop();
+            }
+        }
     }
 
     sand_blocks
 }
 
-fn simulate_sand_void(board: &mut [bool], bounds: &Rect) -> bool {
-    let mut sand_idx = 500 - bounds.left as usize;
-
-    loop {
-        let down_pos = sand_idx + bounds.width();
-        // println!("{sand_idx} {down_pos}");
-
-        match (
-            board.get(down_pos).copied(),
-            board.get(down_pos - 1).copied(),
-            board.get(down_pos + 1).copied(),
-        ) {
-            (Some(false), _, _) => {
-                sand_idx = down_pos;
-            }
-            (_, Some(false), _) => {
-                sand_idx = down_pos - 1;
-            }
-            (_, _, Some(false)) => {
-                sand_idx = down_pos + 1;
-            }
-            (Some(true), Some(true), Some(true)) => {
-                board[sand_idx] = true;
-                return true;
-            }
-            (None, _, _) => return false,
-            _ => unreachable!(),
-        }
-    }
+/// Finds where a grain of sand dropped at `pos` falls to, or `None` if it comes to rest.
+///
+/// A position outside the board means the grain has left it.
+fn next_sand_pos(board: &Grid<bool>, (x, y): Pos) -> Option<Option<Pos>> {
+    [Some(x), x.checked_sub(1), Some(x + 1)]
+        .into_iter()
+        .map(|x| x.map(|x| (x, y + 1)))
+        .find(|&pos| pos.is_none_or(|pos| board.get(pos) != Some(&true)))
 }
 
 #[aoc(day14, part2)]
-pub fn run_part2(input: &str) -> i64 {
-    let polygons = parse_polygons(input.as_bytes());
-
+pub fn run_part2(polygons: &[Polygon]) -> i64 {
     let mut bounds: Rect = polygons.iter().map(|p| p.bounds()).collect();
     bounds.top = 0;
     bounds.bottom += 2;
-    bounds.left -= 200;
-    bounds.right += 200;
 
-    let mut board = vec![false; bounds.area()];
+    // The pile can't spread further sideways than it is tall, with a spare column either side.
+    let spread = bounds.bottom + 1;
+    bounds.left = min(bounds.left, 500 - spread);
+    bounds.right = max(bounds.right, 500 + spread);
 
-    board[(500 - bounds.left) as usize] = true;
+    let mut board = Grid::filled(bounds.width(), bounds.height(), false);
 
     for point in polygons.iter().flat_map(|p| p.points()) {
-        board[point.to_index(&bounds)] = true;
-    }
-
-    let mut sand_blocks = 0;
-
-    while simulate_sand_floor(&mut board, &bounds) {
-        sand_blocks += 1;
+        board[point.to_pos(&bounds)] = true;
     }
 
-    sand_blocks + 1
-}
-
-fn simulate_sand_floor(board: &mut [bool], bounds: &Rect) -> bool {
-    let start = (500 - bounds.left) as usize;
-    let floor = bounds.width() * bounds.bottom as usize;
-
-    let mut sand_idx = start;
+    // Sand ends up on every square above the floor it can reach: those that aren't rock, below a
+    // square with sand or one diagonally beside it.
+    let (start_x, _) = Vertex { x: 500, y: 0 }.to_pos(&bounds);
+    let mut sand = vec![false; board.width()];
+    sand[start_x] = true;
 
-    let last_pos = loop {
-        let down_pos = sand_idx + bounds.width();
+    let mut sand_blocks = 1;
 
-        if down_pos >= floor {
-            board[sand_idx] = true;
-            return true;
-        }
+    for y in 1..board.height() - 1 {
+        let above = std::mem::replace(&mut sand, vec![false; board.width()]);
 
-        match (
-            board.get(down_pos).copied(),
-            board.get(down_pos - 1).copied(),
-            board.get(down_pos + 1).copied(),
-        ) {
-            (Some(false), _, _) => {
-                sand_idx = down_pos;
-            }
-            (_, Some(false), _) => {
-                sand_idx = down_pos - 1;
-            }
-            (_, _, Some(false)) => {
-                sand_idx = down_pos + 1;
-            }
-            (Some(true), Some(true), Some(true)) => {
-                board[sand_idx] = true;
-                break sand_idx;
+        for x in 1..board.width() - 1 {
+            if !board[(x, y)] && above[x - 1..=x + 1].contains(&true) {
+                sand[x] = true;
+                sand_blocks += 1;
             }
-            _ => unreachable!(),
         }
-    };
+    }
 
-    last_pos != start
+    sand_blocks
 }
 
 #[cfg(test)]
@@ -332,11 +361,48 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 24);
+        assert_eq!(
+            run_part1(&parse_polygons(TEST_INPUT.as_bytes()).unwrap()),
+            24
+        );
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 93);
+        assert_eq!(
+            run_part2(&parse_polygons(TEST_INPUT.as_bytes()).unwrap()),
+            93
+        );
+    }
+
+    #[test]
+    fn far_rocks() {
+        // Further to the side than 16-bit coordinates reach.
+        let polygons = parse_polygons(b"40000,1 -> 40002,1").unwrap();
+
+        assert_eq!(run_part1(&polygons), 0);
+        assert_eq!(run_part2(&polygons), 9);
+    }
+
+    #[test]
+    fn malformed_path() {
+        let error = parse_polygons(b"498,4 -> 498,6\n503,4 -> 502;4").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.found), (2, 13, Some(';')));
+    }
+
+    #[test]
+    fn rocks_outside_the_cave() {
+        let error = parse_polygons(b"498,4 -> 498,6\n10,10 -> 20,20").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (2, 10, Some('2')));
+
+        let error = parse_polygons(b"498,-4 -> 498,6").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (1, 5, Some('-')));
+
+        let error = parse_polygons(b"0,0 -> 0,1000000").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (1, 10, Some('1')));
+
+        let error = parse_polygons(b"100001,5 -> 500,5").unwrap_err();
+        assert_eq!((error.line, error.column, error.found), (1, 1, Some('1')));
     }
 }
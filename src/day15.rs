@@ -1,26 +1,245 @@
+use std::cmp::max;
+
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::i64 as parse_number,
+    combinator::{all_consuming, cut, map},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+    Finish, IResult,
+};
+
+use crate::{error::ParseError, solution::Solution};
+
+const DAY: u8 = 15;
+
+const ROW: i64 = 2_000_000;
+const SEARCH_SIZE: i64 = 4_000_000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl Point {
+    fn distance(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Sensor {
+    position: Point,
+    beacon: Point,
+    radius: i64,
+}
+
+impl Sensor {
+    fn new(position: Point, beacon: Point) -> Self {
+        Self {
+            position,
+            beacon,
+            radius: position.distance(beacon),
+        }
+    }
+
+    fn covers(&self, point: Point) -> bool {
+        self.position.distance(point) <= self.radius
+    }
+
+    /// The inclusive range of columns this sensor covers on `row`, if any.
+    fn coverage_on_row(&self, row: i64) -> Option<(i64, i64)> {
+        let spread = self.radius - (self.position.y - row).abs();
+        (spread >= 0).then_some((self.position.x - spread, self.position.x + spread))
+    }
+}
+
+fn parse_point(i: &[u8]) -> IResult<&[u8], Point> {
+    let pair = separated_pair(
+        preceded(tag("x="), parse_number),
+        tag(", "),
+        preceded(tag("y="), parse_number),
+    );
+
+    map(pair, |(x, y)| Point { x, y })(i)
+}
+
+fn parse_sensor(i: &[u8]) -> IResult<&[u8], Sensor> {
+    let sensor = preceded(
+        tag("Sensor at "),
+        cut(separated_pair(
+            parse_point,
+            tag(": closest beacon is at "),
+            parse_point,
+        )),
+    );
+
+    map(sensor, |(position, beacon)| Sensor::new(position, beacon))(i)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = DAY;
+    const NAME: Option<&'static str> = Some("Beacon Exclusion Zone");
+
+    type Input = Vec<Sensor>;
+    type Output1 = i64;
+    type Output2 = Result<i64, &'static str>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_sensors(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output1 {
+        run_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Output2 {
+        run_part2(input)
+    }
+}
+
+#[aoc_generator(day15)]
+pub fn parse_sensors(i: &[u8]) -> Result<Vec<Sensor>, ParseError> {
+    all_consuming(separated_list1(tag("\n"), cut(parse_sensor)))(i)
+        .finish()
+        .map(|(_, v)| v)
+        .map_err(|e| ParseError::at_offset(DAY, i, i.len() - e.input.len(), "sensor report"))
+}
+
+/// Merges the coverage of every sensor on `row` into sorted, non-overlapping ranges.
+fn merged_coverage(sensors: &[Sensor], row: i64) -> Vec<(i64, i64)> {
+    let mut ranges = sensors
+        .iter()
+        .filter_map(|s| s.coverage_on_row(row))
+        .collect::<Vec<_>>();
+
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(ranges.len());
+
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end + 1 => *last_end = max(*last_end, end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+pub(crate) fn excluded_positions(sensors: &[Sensor], row: i64) -> i64 {
+    let coverage = merged_coverage(sensors, row);
+
+    let covered: i64 = coverage.iter().map(|(start, end)| end - start + 1).sum();
+    let beacons = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|b| b.y == row)
+        .unique()
+        .filter(|b| {
+            coverage
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&b.x))
+        })
+        .count() as i64;
+
+    covered - beacons
+}
+
+/// Finds the only position within `0..=size` on both axes that no sensor covers.
+///
+/// That position has to sit just outside the range of several sensors, so rather than scanning
+/// every row it's enough to check where the diagonal lines bordering each sensor's range cross.
+pub(crate) fn find_distress_beacon(sensors: &[Sensor], size: i64) -> Option<Point> {
+    // Lines of the form `x + y = a` and `x - y = b`.
+    let mut ascending = Vec::with_capacity(sensors.len() * 2);
+    let mut descending = Vec::with_capacity(sensors.len() * 2);
+
+    for sensor in sensors {
+        let Point { x, y } = sensor.position;
+        let reach = sensor.radius + 1;
+
+        ascending.extend([x + y - reach, x + y + reach]);
+        descending.extend([x - y - reach, x - y + reach]);
+    }
+
+    let corners = [(0, 0), (size, 0), (0, size), (size, size)].map(|(x, y)| Point { x, y });
+
+    ascending
+        .iter()
+        .cartesian_product(&descending)
+        .filter(|(a, b)| (*a - *b) % 2 == 0)
+        .map(|(a, b)| Point {
+            x: (a + b) / 2,
+            y: (a - b) / 2,
+        })
+        .chain(corners)
+        .filter(|p| (0..=size).contains(&p.x) && (0..=size).contains(&p.y))
+        .find(|&p| sensors.iter().all(|s| !s.covers(p)))
+}
+
+pub(crate) fn tuning_frequency(beacon: Point) -> i64 {
+    beacon.x * 4_000_000 + beacon.y
+}
+
 #[aoc(day15, part1)]
-pub fn run_part1(input: &str) -> i64 {
-    todo!()
+pub fn run_part1(sensors: &[Sensor]) -> i64 {
+    excluded_positions(sensors, ROW)
 }
 
-/* #[aoc(day15, part2)]
-pub fn run_part2(input: &str) -> i64 {
-    todo!()
-} */
+#[aoc(day15, part2)]
+pub fn run_part2(sensors: &[Sensor]) -> Result<i64, &'static str> {
+    let beacon = find_distress_beacon(sensors, SEARCH_SIZE).ok_or("no uncovered position")?;
+
+    Ok(tuning_frequency(beacon))
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
     #[test]
     fn part1_example() {
-        assert_eq!(run_part1(TEST_INPUT), 0);
+        let sensors = parse_sensors(TEST_INPUT.as_bytes()).unwrap();
+        assert_eq!(excluded_positions(&sensors, 10), 26);
     }
 
-    /* #[test]
+    #[test]
     fn part2_example() {
-        assert_eq!(run_part2(TEST_INPUT), 0);
-    } */
+        let sensors = parse_sensors(TEST_INPUT.as_bytes()).unwrap();
+        let beacon = find_distress_beacon(&sensors, 20).unwrap();
+
+        assert_eq!(beacon, Point { x: 14, y: 11 });
+        assert_eq!(tuning_frequency(beacon), 56000011);
+    }
+
+    #[test]
+    fn merges_touching_ranges() {
+        let sensors = [
+            Sensor::new(Point { x: 0, y: 0 }, Point { x: 2, y: 0 }),
+            Sensor::new(Point { x: 4, y: 0 }, Point { x: 5, y: 0 }),
+        ];
+
+        assert_eq!(merged_coverage(&sensors, 0), [(-2, 5)]);
+        assert_eq!(merged_coverage(&sensors, 2), [(0, 0)]);
+    }
 }
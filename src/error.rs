@@ -0,0 +1,137 @@
+use std::{error::Error, fmt::Display, io};
+
+/// Malformed puzzle input, pointing at the first offending byte.
+///
+/// Lines and columns are 1-based. `found` is `None` when the line or input ended early.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: &'static str,
+    pub found: Option<char>,
+}
+
+impl ParseError {
+    /// An error for the byte at `index` of a single line.
+    pub fn unexpected(day: u8, line: &[u8], index: usize, expected: &'static str) -> Self {
+        Self {
+            day,
+            line: 1,
+            column: index + 1,
+            expected,
+            found: line.get(index).map(|&b| b as char),
+        }
+    }
+
+    /// An error for the byte at `offset` of the whole input, working out its line and column.
+    pub fn at_offset(day: u8, input: &[u8], offset: usize, expected: &'static str) -> Self {
+        let preceding = &input[..offset.min(input.len())];
+        let line_start = preceding
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+
+        Self {
+            day,
+            line: preceding.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: offset - line_start + 1,
+            expected,
+            found: input
+                .get(offset)
+                .filter(|&&b| b != b'\n')
+                .map(|&b| b as char),
+        }
+    }
+
+    /// Moves an error produced for a single line down to the line with index `line_idx`.
+    pub fn on_line(mut self, line_idx: usize) -> Self {
+        self.line += line_idx;
+        self
+    }
+
+    /// Moves an error produced for a sub-slice right by the `columns` that preceded it.
+    pub fn shifted(mut self, columns: usize) -> Self {
+        self.column += columns;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} input, line {}, column {}: expected {}, found ",
+            self.day, self.line, self.column, self.expected
+        )?;
+
+        match self.found {
+            Some(found) => write!(f, "{found:?}"),
+            None => f.write_str("end of line"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Puzzle input streamed from a reader that failed to read or parse.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "can't read input: {error}"),
+            Self::Parse(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_offset() {
+        let error = ParseError::at_offset(1, b"12\n3x4\n", 4, "digit");
+
+        assert_eq!((error.line, error.column, error.found), (2, 2, Some('x')));
+        assert_eq!(
+            error.to_string(),
+            "day 1 input, line 2, column 2: expected digit, found 'x'"
+        );
+    }
+
+    #[test]
+    fn relocates_line_errors() {
+        let error = ParseError::unexpected(4, b"2-", 2, "digit")
+            .shifted(4)
+            .on_line(2);
+
+        assert_eq!((error.line, error.column, error.found), (3, 7, None));
+    }
+}
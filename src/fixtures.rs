@@ -0,0 +1,184 @@
+//! The puzzle examples, and any other small inputs worth keeping, as files.
+//!
+//! Each day's fixtures are `examples/2022/day<NN>/<name>.txt`, with their expected answers in an
+//! `answers.toml` next to them as `[<name>]` tables of `part<M>` strings. Every fixture is run
+//! through every registered solver of its day, so covering an edge case only takes a new file and
+//! its answers.
+//!
+//! Day 15's example asks about a different row and area than the real puzzle, so it's still tested
+//! in its own module.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml::Table;
+
+use crate::{input, registry};
+
+const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/2022");
+
+/// A fixture file with the answers expected for it.
+#[derive(Debug)]
+struct Fixture {
+    day: u8,
+    name: String,
+    path: PathBuf,
+    answers: Vec<(u8, String)>,
+}
+
+fn parse_key(key: &str, prefix: &str) -> Option<u8> {
+    key.strip_prefix(prefix)?.parse().ok()
+}
+
+/// The fixtures in one day's directory, sorted by name.
+///
+/// Fails if a fixture has no answers or an answer has no fixture, since either is a mistake.
+fn day_fixtures(dir: &Path, day: u8) -> Result<Vec<Fixture>, String> {
+    let answers_path = dir.join("answers.toml");
+    let mut answers = fs::read_to_string(&answers_path)
+        .map_err(|e| format!("can't read {}: {e}", answers_path.display()))?
+        .parse::<Table>()
+        .map_err(|e| format!("{}: {e}", answers_path.display()))?;
+
+    let mut fixtures = Vec::new();
+
+    for entry in fs::read_dir(dir).map_err(|e| format!("can't list {}: {e}", dir.display()))? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+
+        if path.extension().is_none_or(|e| e != "txt") {
+            continue;
+        }
+
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| format!("bad fixture name {}", path.display()))?
+            .to_owned();
+
+        let parts = answers
+            .remove(&name)
+            .ok_or_else(|| format!("{} has no answers", path.display()))?;
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("`{name}` in {} should be a table", answers_path.display()))?;
+
+        let mut expected = Vec::new();
+
+        for (key, answer) in parts {
+            match (parse_key(key, "part"), answer.as_str()) {
+                (Some(part), Some(answer)) => expected.push((part, answer.to_owned())),
+                _ => return Err(format!("bad answer `{name}.{key}`")),
+            }
+        }
+
+        fixtures.push(Fixture {
+            day,
+            name,
+            path,
+            answers: expected,
+        });
+    }
+
+    if let Some(name) = answers.keys().next() {
+        return Err(format!(
+            "{} has answers for `{name}` but no fixture",
+            answers_path.display()
+        ));
+    }
+
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(fixtures)
+}
+
+/// Every fixture under `dir`, by day.
+fn find_fixtures(dir: &Path) -> Result<Vec<Fixture>, String> {
+    let mut days = Vec::new();
+
+    for entry in fs::read_dir(dir).map_err(|e| format!("can't list {}: {e}", dir.display()))? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        match parse_key(name, "day") {
+            Some(day) if path.is_dir() => days.push((day, path)),
+            _ => return Err(format!("expected a directory like `day01`, found {name:?}")),
+        }
+    }
+
+    days.sort();
+
+    let mut fixtures = Vec::new();
+
+    for (day, path) in days {
+        fixtures.extend(day_fixtures(&path, day)?);
+    }
+
+    Ok(fixtures)
+}
+
+#[test]
+fn fixtures_match_answers() {
+    let fixtures = find_fixtures(Path::new(FIXTURE_DIR)).unwrap_or_else(|e| panic!("{e}"));
+    let mut failures = Vec::new();
+
+    for fixture in &fixtures {
+        let text = fs::read_to_string(&fixture.path).unwrap();
+        let text = input::normalize(&text);
+
+        for (part, expected) in &fixture.answers {
+            let label = format!("day{} part{part} [{}]", fixture.day, fixture.name);
+            let solvers = registry::find(Some(fixture.day), Some(*part), None).collect::<Vec<_>>();
+
+            if solvers.is_empty() {
+                failures.push(format!("{label}: no solvers"));
+            }
+
+            for solver in solvers {
+                match solver.run(&text) {
+                    Ok(answer) if answer == *expected => (),
+                    Ok(answer) => failures.push(format!(
+                        "{label} {}: expected {expected:?}, got {answer:?}",
+                        solver.variant
+                    )),
+                    Err(e) => failures.push(format!("{label} {}: {e}", solver.variant)),
+                }
+            }
+        }
+    }
+
+    assert!(!fixtures.is_empty());
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn needs_answers_for_every_fixture() {
+    let dir = std::env::temp_dir().join(format!("aoc_2022_fixtures_{}", std::process::id()));
+    let day_dir = dir.join("day01");
+    fs::create_dir_all(&day_dir).unwrap();
+
+    fs::write(day_dir.join("a.txt"), "1").unwrap();
+    fs::write(day_dir.join("b.txt"), "2").unwrap();
+    fs::write(day_dir.join("answers.toml"), "[a]\npart1 = \"1\"").unwrap();
+    let missing = find_fixtures(&dir).map(|_| ());
+
+    fs::write(day_dir.join("answers.toml"), "[a]\npart1 = \"1\"\n[b]\n[c]").unwrap();
+    let extra = find_fixtures(&dir).map(|_| ());
+
+    fs::write(
+        day_dir.join("answers.toml"),
+        "[a]\npart1 = \"1\"\n[b]\npart2 = \"2\"",
+    )
+    .unwrap();
+    let fixtures = find_fixtures(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(missing.unwrap_err().ends_with("b.txt has no answers"));
+    assert!(extra
+        .unwrap_err()
+        .ends_with("answers for `c` but no fixture"));
+
+    let names = fixtures.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b"]);
+    assert_eq!(fixtures[1].answers, [(2, "2".to_owned())]);
+}
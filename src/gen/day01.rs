@@ -0,0 +1,13 @@
+use crate::rng::Rng;
+
+/// Calorie lists for `size` elves, each carrying a handful of items.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves = (0..size)
+        .map(|_| {
+            let items = (0..rng.range(1..=6)).map(|_| rng.range(1..=60_000).to_string());
+            items.collect::<Vec<_>>().join("\n")
+        })
+        .collect::<Vec<_>>();
+
+    elves.join("\n\n")
+}
@@ -0,0 +1,16 @@
+use crate::rng::Rng;
+
+/// A strategy guide of `size` rounds.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let rounds = (0..size)
+        .map(|_| {
+            format!(
+                "{} {}",
+                *rng.pick(b"ABC") as char,
+                *rng.pick(b"XYZ") as char
+            )
+        })
+        .collect::<Vec<_>>();
+
+    rounds.join("\n")
+}
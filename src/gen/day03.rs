@@ -0,0 +1,52 @@
+use super::letters;
+use crate::rng::Rng;
+
+/// `size` groups of three rucksacks.
+///
+/// Only the group's badge is shared by all three, and each rucksack has exactly one item type in
+/// both compartments.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut rucksacks = Vec::new();
+
+    for _ in 0..size {
+        let mut letters = letters();
+        rng.shuffle(&mut letters);
+        let badge = letters.pop().unwrap();
+
+        // Each rucksack of the group draws from its own letters, so only the badge is shared.
+        for own in letters.chunks(17) {
+            let (left_letters, right_letters) = own.split_at(8);
+            let shared = if rng.chance(0.3) {
+                badge
+            } else {
+                *rng.pick(own)
+            };
+
+            let (mut left, mut right) = (vec![shared], vec![shared]);
+
+            if shared != badge {
+                match rng.chance(0.5) {
+                    true => left.push(badge),
+                    false => right.push(badge),
+                }
+            }
+
+            let half = rng.range(2..=16) as usize;
+
+            while left.len() < half {
+                left.push(*rng.pick(left_letters));
+            }
+
+            while right.len() < half {
+                right.push(*rng.pick(right_letters));
+            }
+
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            left.extend(right);
+            rucksacks.push(String::from_utf8(left).unwrap());
+        }
+    }
+
+    rucksacks.join("\n")
+}
@@ -0,0 +1,15 @@
+use crate::rng::Rng;
+
+/// `size` pairs of section assignments.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let (a, b) = (rng.range(1..=99), rng.range(1..=99));
+        format!("{}-{}", a.min(b), a.max(b))
+    };
+
+    let pairs = (0..size)
+        .map(|_| format!("{},{}", range(rng), range(rng)))
+        .collect::<Vec<_>>();
+
+    pairs.join("\n")
+}
@@ -0,0 +1,66 @@
+use crate::rng::Rng;
+
+/// Stacks of crates followed by `size` moves.
+///
+/// Most drawings have up to nine short stacks like the puzzle's, but some have dozens of stacks
+/// or stacks a hundred crates tall.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = if rng.chance(0.2) {
+        rng.range(10..=40)
+    } else {
+        rng.range(2..=9)
+    };
+
+    let mut stacks = (0..count)
+        .map(|_| {
+            let height = if rng.chance(0.1) {
+                rng.range(60..=100)
+            } else {
+                rng.range(2..=6)
+            };
+            (0..height)
+                .map(|_| rng.range(b'A' as i64..=b'Z' as i64) as u8 as char)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+    let mut lines = Vec::new();
+
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(item) => format!("[{item}]"),
+                None => "   ".to_owned(),
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(row.join(" "));
+    }
+
+    let numbers = (1..=stacks.len())
+        .map(|i| format!("{i:^3}"))
+        .collect::<Vec<_>>();
+    lines.push(numbers.join(" "));
+    lines.push(String::new());
+
+    // With at least two crates per stack there's always one stack holding more than one.
+    for _ in 0..size {
+        let sources = (0..stacks.len())
+            .filter(|&i| stacks[i].len() > 1)
+            .collect::<Vec<_>>();
+
+        let from = *rng.pick(&sources);
+        let to = (from + 1 + rng.below(stacks.len() - 1)) % stacks.len();
+        let count = rng.range(1..=stacks[from].len() as i64 - 1) as usize;
+
+        let split = stacks[from].len() - count;
+        let items = stacks[from].split_off(split);
+        stacks[to].extend(items);
+
+        lines.push(format!("move {count} from {} to {}", from + 1, to + 1));
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,14 @@
+use super::letters;
+use crate::rng::Rng;
+
+/// A datastream of `size` characters from a small alphabet, ending in a 14 character marker.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let alphabet = &letters()[..rng.range(2..=16) as usize];
+    let mut signal = (0..size).map(|_| *rng.pick(alphabet)).collect::<Vec<_>>();
+
+    let mut marker = letters()[..26].to_vec();
+    rng.shuffle(&mut marker);
+    signal.extend(&marker[..14]);
+
+    String::from_utf8(signal).unwrap()
+}
@@ -0,0 +1,75 @@
+use crate::rng::Rng;
+
+/// Short distinct names for the entries of a directory: `a` to `z`, then `aa`, `ab` and so on.
+fn name(mut index: usize) -> String {
+    let mut name = Vec::new();
+
+    loop {
+        name.push(b'a' + (index % 26) as u8);
+        index /= 26;
+
+        if index == 0 {
+            break;
+        }
+
+        index -= 1;
+    }
+
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+fn explore(rng: &mut Rng, children: &[Vec<usize>], dir: usize, lines: &mut Vec<String>) {
+    let dirs = &children[dir];
+    let mut entries = (0..dirs.len())
+        .map(|i| format!("dir {}", name(i)))
+        .collect::<Vec<_>>();
+
+    for file in 0..rng.below(5) {
+        let size = match rng.below(4) {
+            0 => rng.range(1..=1_000),
+            1 => rng.range(1_000..=100_000),
+            2 => rng.range(100_000..=5_000_000),
+            _ => rng.range(5_000_000..=30_000_000),
+        };
+
+        entries.push(format!("{size} {}.dat", name(file)));
+    }
+
+    rng.shuffle(&mut entries);
+    lines.push("$ ls".to_owned());
+    lines.extend(entries);
+
+    for (i, &child) in dirs.iter().enumerate() {
+        lines.push(format!("$ cd {}", name(i)));
+        explore(rng, children, child, lines);
+        lines.push("$ cd ..".to_owned());
+    }
+}
+
+/// A transcript exploring `size` directories below the root, each holding a few files.
+///
+/// Every directory is listed exactly once, in depth first order.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut children = vec![Vec::new(); size + 1];
+
+    for dir in 1..=size {
+        children[rng.below(dir)].push(dir);
+    }
+
+    let mut lines = vec!["$ cd /".to_owned()];
+    explore(rng, &children, 0, &mut lines);
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_entries() {
+        let names = [0, 1, 25, 26, 27, 701, 702].map(name);
+        assert_eq!(names, ["a", "b", "z", "aa", "ab", "zz", "aaa"]);
+    }
+}
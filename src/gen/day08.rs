@@ -0,0 +1,18 @@
+use crate::rng::Rng;
+
+/// A forest of about `size` trees, and at least one tree in each direction.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.isqrt().max(1);
+    let width = rng.range(side as i64 / 2..=side as i64 * 2).max(1) as usize;
+    let height = (size / width).max(1);
+    let tallest = rng.range(0..=9) as u8;
+
+    let rows = (0..height)
+        .map(|_| {
+            let row = (0..width).map(|_| b'0' + rng.range(0..=tallest as i64) as u8);
+            String::from_utf8(row.collect()).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    rows.join("\n")
+}
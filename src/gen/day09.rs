@@ -0,0 +1,18 @@
+use crate::rng::Rng;
+
+/// `size` rope moves of up to ten steps each, with the odd long excursion of hundreds of steps.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let moves = (0..size)
+        .map(|_| {
+            let count = if rng.chance(0.02) {
+                rng.range(100..=1000)
+            } else {
+                rng.range(1..=10)
+            };
+
+            format!("{} {count}", *rng.pick(b"LRUD") as char)
+        })
+        .collect::<Vec<_>>();
+
+    moves.join("\n")
+}
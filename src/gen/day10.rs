@@ -0,0 +1,19 @@
+use crate::rng::Rng;
+
+/// A program running for `size` cycles, or the 240 the screen needs if that's more.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cycles = size.max(240);
+    let (mut cycle, mut program) = (0, Vec::new());
+
+    while cycle < cycles {
+        if cycle + 1 == cycles || rng.chance(0.3) {
+            program.push("noop".to_owned());
+            cycle += 1;
+        } else {
+            program.push(format!("addx {}", rng.range(-10..=10)));
+            cycle += 2;
+        }
+    }
+
+    program.join("\n")
+}
@@ -0,0 +1,43 @@
+use crate::rng::Rng;
+
+/// Divisors for the monkeys' tests, which multiply out to well past 32 bits with enough monkeys.
+const PRIMES: [u64; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// Two to twelve monkeys, each starting with up to `size` items.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = rng.range(2..=12) as usize;
+    let mut divisors = PRIMES;
+    rng.shuffle(&mut divisors);
+
+    let monkeys = (0..count)
+        .map(|i| {
+            let items = (0..rng.range(1..=size.max(1) as i64))
+                .map(|_| rng.range(1..=99).to_string())
+                .collect::<Vec<_>>();
+
+            let operation = match rng.below(5) {
+                0 => "old * old".to_owned(),
+                1 | 2 => format!("old + {}", rng.range(1..=9)),
+                _ => format!("old * {}", rng.range(2..=19)),
+            };
+
+            let mut other = || (i + 1 + rng.below(count - 1)) % count;
+            let targets = [other(), other()];
+
+            format!(
+                "Monkey {i}:
+  Starting items: {}
+  Operation: new = {operation}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey {}",
+                items.join(", "),
+                divisors[i],
+                targets[0],
+                targets[1]
+            )
+        })
+        .collect::<Vec<_>>();
+
+    monkeys.join("\n\n")
+}
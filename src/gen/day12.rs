@@ -0,0 +1,48 @@
+use crate::rng::Rng;
+
+/// A heightmap of about `size` squares, sloping from `a` on the left to `z` on the right.
+///
+/// It's at least 26 squares wide and two tall, however small `size` is. The start is in the
+/// leftmost column and the goal in the rightmost. The slope is bumpy, but a path between them is
+/// always kept smooth enough to climb.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.isqrt();
+    let width = rng.range(side as i64 / 2..=side as i64 * 2).max(26) as usize;
+    let height = (size / width).max(2);
+
+    // With at least 26 columns, the slope rises by at most one level per column.
+    let slope = |x: usize| (25 * x / (width - 1)) as u8;
+
+    let mut rows = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|x| {
+                    let level = slope(x) as i64 + rng.range(-2..=1);
+                    b'a' + level.clamp(0, 25) as u8
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let (start_row, goal_row) = (rng.below(height), rng.below(height));
+    let turn = rng.below(width);
+
+    // Along the start row, across at the turn, then along the goal row.
+    for (x, square) in rows[start_row].iter_mut().enumerate().take(turn) {
+        *square = b'a' + slope(x);
+    }
+
+    for row in &mut rows[start_row.min(goal_row)..=start_row.max(goal_row)] {
+        row[turn] = b'a' + slope(turn);
+    }
+
+    for (x, square) in rows[goal_row].iter_mut().enumerate().skip(turn) {
+        *square = b'a' + slope(x);
+    }
+
+    rows[start_row][0] = b'S';
+    rows[goal_row][width - 1] = b'E';
+
+    let rows = rows.into_iter().map(|row| String::from_utf8(row).unwrap());
+    rows.collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,92 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::rng::Rng;
+
+/// A packet as the puzzle describes it, ordered by its rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    /// The divider packets `[[2]]` and `[[6]]`.
+    pub(crate) fn dividers() -> [Self; 2] {
+        [2, 6].map(|n| Self::List(vec![Self::List(vec![Self::Int(n)])]))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => a.cmp(b),
+            (Self::List(a), Self::List(b)) => a.cmp(b),
+            (Self::Int(_), Self::List(_)) => Self::List(vec![self.clone()]).cmp(other),
+            (Self::List(_), Self::Int(_)) => self.cmp(&Self::List(vec![other.clone()])),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(n) => write!(f, "{n}"),
+            Self::List(items) => {
+                let items = items.iter().map(Self::to_string).collect::<Vec<_>>();
+                write!(f, "[{}]", items.join(","))
+            }
+        }
+    }
+}
+
+fn random_packet(rng: &mut Rng, depth: u32) -> Packet {
+    if depth > 0 && rng.chance(0.6) {
+        Packet::Int(rng.range(0..=10) as u32)
+    } else if depth < 4 {
+        let items = (0..rng.below(5))
+            .map(|_| random_packet(rng, depth + 1))
+            .collect();
+        Packet::List(items)
+    } else {
+        Packet::List(Vec::new())
+    }
+}
+
+/// `size` pairs of packets in no particular order, leaving out ties that make either part
+/// ambiguous.
+///
+/// Lists have at most four items and are nested at most five deep, well within what the solver
+/// parses.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let dividers = Packet::dividers();
+    let packet = |rng: &mut Rng| loop {
+        let packet = random_packet(rng, 0);
+
+        if dividers.iter().all(|d| packet.cmp(d).is_ne()) {
+            return packet;
+        }
+    };
+
+    let pairs = (0..size)
+        .map(|_| {
+            let left = packet(rng);
+            let right = loop {
+                let right = packet(rng);
+
+                if right.cmp(&left).is_ne() {
+                    break right;
+                }
+            };
+
+            format!("{left}\n{right}")
+        })
+        .collect::<Vec<_>>();
+
+    pairs.join("\n\n")
+}
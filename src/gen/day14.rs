@@ -0,0 +1,33 @@
+use crate::rng::Rng;
+
+/// `size` rock paths of a few segments each, spread over a cave of about `8 * size` squares.
+///
+/// The rocks all lie below the source at least as far as they reach to either side of it, so
+/// sand always falls into the abyss before the source is blocked.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let spread = 10 + 2 * size.isqrt() as i64;
+    let (left, right) = (500 - spread, 500 + spread);
+    let (top, bottom) = (spread + 1, 2 * spread + 4);
+
+    let paths = (0..size)
+        .map(|_| {
+            let (mut x, mut y) = (rng.range(left..=right), rng.range(top..=bottom));
+            let mut vertices = vec![format!("{x},{y}")];
+            let mut horizontal = rng.chance(0.5);
+
+            for _ in 0..rng.range(1..=3) {
+                match horizontal {
+                    true => x = (x + rng.range(-6..=6)).clamp(left, right),
+                    false => y = (y + rng.range(-4..=4)).clamp(top, bottom),
+                }
+
+                vertices.push(format!("{x},{y}"));
+                horizontal = !horizontal;
+            }
+
+            vertices.join(" -> ")
+        })
+        .collect::<Vec<_>>();
+
+    paths.join("\n")
+}
@@ -0,0 +1,81 @@
+//! Random puzzle inputs of any size, for stress testing the solvers.
+//!
+//! Every generator takes a seeded [`Rng`] and a size, which is roughly the number of records in
+//! the input: elves, rounds, moves and so on. Each day documents what its size counts. The same
+//! seed and size always give the same input.
+
+use crate::rng::Rng;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+
+/// Makes an input of the given size.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// The generator for each day that has one.
+pub const GENERATORS: &[(u8, Generator)] = &[
+    (1, day01::generate),
+    (2, day02::generate),
+    (3, day03::generate),
+    (4, day04::generate),
+    (5, day05::generate),
+    (6, day06::generate),
+    (7, day07::generate),
+    (8, day08::generate),
+    (9, day09::generate),
+    (10, day10::generate),
+    (11, day11::generate),
+    (12, day12::generate),
+    (13, day13::generate),
+    (14, day14::generate),
+];
+
+pub fn find(day: u8) -> Option<Generator> {
+    GENERATORS
+        .iter()
+        .find(|&&(d, _)| d == day)
+        .map(|&(_, generator)| generator)
+}
+
+/// Generates an input for `day` from `seed`, if the day has a generator.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    find(day).map(|generator| generator(&mut Rng::new(seed), size))
+}
+
+/// Letters `a` to `z` followed by `A` to `Z`.
+fn letters() -> Vec<u8> {
+    (b'a'..=b'z').chain(b'A'..=b'Z').collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn generates_parsable_inputs() {
+        for &(day, _) in GENERATORS {
+            let input = generate(day, 50, 1).unwrap();
+            assert_eq!(generate(day, 50, 1), Some(input.clone()));
+
+            for solver in registry::find(Some(day), None, None) {
+                let answer = solver.run(&input);
+                assert!(answer.is_ok(), "day {day}: {answer:?}");
+            }
+        }
+
+        assert_eq!(generate(25, 50, 1), None);
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::error::ParseError;
+
+/// A `(x, y)` coordinate into a [`Grid`], with `y` growing downwards.
+pub type Pos = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored in row-major order, sized at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "cell count doesn't match a {width}x{height} grid"
+        );
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[inline]
+    pub fn index_of(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+
+    #[inline]
+    pub fn pos_of(&self, index: usize) -> Pos {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index_of(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index_of(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Every position in the grid, in storage order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offset_positions(pos, &OFFSETS_4)
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offset_positions(pos, &OFFSETS_8)
+    }
+
+    fn offset_positions<'a>(
+        &'a self,
+        (x, y): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.contains(pos).then_some(pos)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl Grid<u8> {
+    /// Builds a grid from newline separated rows of bytes, taking the width from the first row.
+    ///
+    /// Empty lines are skipped, so a trailing newline is accepted.
+    pub fn from_bytes(input: &[u8]) -> Self {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for row in input.split(|&b| b == b'\n').filter(|r| !r.is_empty()) {
+            let width = *width.get_or_insert(row.len());
+            assert_eq!(row.len(), width, "grid rows must have the same length");
+
+            cells.extend_from_slice(row);
+        }
+
+        let width = width.unwrap_or(0);
+        let height = cells.len().checked_div(width).unwrap_or(0);
+
+        Self::new(width, height, cells)
+    }
+
+    /// Like [`Grid::from_bytes`], but reports cells rejected by `is_cell` and ragged rows.
+    pub fn parse(
+        day: u8,
+        input: &[u8],
+        is_cell: impl Fn(u8) -> bool,
+        expected: &'static str,
+    ) -> Result<Self, ParseError> {
+        let width = input
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(input.len());
+
+        for (i, row) in input
+            .split(|&b| b == b'\n')
+            .enumerate()
+            .filter(|(_, row)| !row.is_empty())
+        {
+            let error =
+                |index, expected| ParseError::unexpected(day, row, index, expected).on_line(i);
+
+            if let Some(index) = row.iter().position(|&b| !is_cell(b)) {
+                return Err(error(index, expected));
+            }
+
+            if row.len() != width {
+                return Err(error(row.len().min(width), "row as wide as the first"));
+            }
+        }
+
+        Ok(Self::from_bytes(input))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.contains(pos), "{pos:?} is outside the grid");
+        let index = self.index_of(pos);
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y != 0 {
+                f.write_str("\n")?;
+            }
+
+            for cell in row {
+                cell.fmt(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "abc
+def
+";
+
+    #[test]
+    fn detects_dimensions() {
+        let grid = Grid::from_bytes(TEST_INPUT.as_bytes());
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], b'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::from_bytes(TEST_INPUT.as_bytes());
+
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::from_bytes(TEST_INPUT.as_bytes());
+
+        assert_eq!(grid.row(1), b"def");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.map(|&b| b as char).to_string(), "abc\ndef");
+    }
+}
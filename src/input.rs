@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+
+const BOM: char = '\u{feff}';
+
+/// Brings puzzle input into the shape every solver expects.
+///
+/// Strips a leading byte order mark, turns CRLF line endings into LF and drops trailing
+/// newlines, the same as `cargo aoc` does. Input that's already normalized is borrowed as is.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+
+    if input.contains('\r') {
+        let input = input.replace("\r\n", "\n");
+        Cow::Owned(input.trim_end_matches('\n').to_owned())
+    } else {
+        Cow::Borrowed(input.trim_end_matches('\n'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{registry, runner};
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("1\n2"), "1\n2");
+        assert_eq!(normalize("1\n2\n\n"), "1\n2");
+        assert_eq!(normalize("\u{feff}1\r\n\r\n2\r\n"), "1\n\n2");
+        assert!(matches!(normalize("1\n2\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn every_day_accepts_both_line_endings() {
+        for solver in registry::SOLVERS {
+            for input in runner::find_inputs(solver.day).unwrap() {
+                let input = input.load().unwrap();
+                let expected = solver.run(&input).unwrap();
+                let label = format!("day{} part{}", solver.day, solver.part);
+
+                let unix = format!("{input}\n");
+                let windows = format!("{BOM}{}\r\n", input.replace('\n', "\r\n"));
+
+                for (form, raw) in [("LF", unix), ("CRLF", windows)] {
+                    let answer = solver.run(&normalize(&raw)).unwrap();
+                    assert_eq!(answer, expected, "{label}, {form}");
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-#![feature(byte_slice_trim_ascii, iter_order_by)]
+#![feature(iter_order_by)]
 
 extern crate aoc_runner;
 
@@ -6,6 +6,21 @@ extern crate aoc_runner;
 extern crate aoc_runner_derive;
 extern crate crypto;
 
+pub mod error;
+#[cfg(test)]
+mod fixtures;
+pub mod gen;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod parse;
+#[cfg(test)]
+mod reference;
+pub mod registry;
+pub mod rng;
+pub mod runner;
+pub mod solution;
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -22,4 +37,11 @@ pub mod day13;
 pub mod day14;
 pub mod day15;
 
+pub use registry::solve;
+pub use solution::Solution;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
 aoc_lib! { year = 2022 }
@@ -1,7 +1,5 @@
-extern crate aoc_2022;
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
+use std::process::ExitCode;
 
-use aoc_runner_derive::aoc_main;
-
-aoc_main! { lib = aoc_2022 }
+fn main() -> ExitCode {
+    aoc_2022::runner::main(std::env::args().skip(1))
+}
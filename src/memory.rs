@@ -0,0 +1,181 @@
+//! Heap usage of the solvers, as counted by [`CountingAllocator`].
+//!
+//! The crate only installs the allocator when built with the `count-allocations` feature, since
+//! counting slows every allocation down. Counts are kept per thread, so solvers running side by
+//! side don't see each other's allocations.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Whether this build counts allocations, so [`measure`] has anything to report.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+/// The system allocator, counting what each thread allocates.
+pub struct CountingAllocator;
+
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    current: usize,
+    peak: usize,
+    allocations: u64,
+    allocated: u64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            current: 0,
+            peak: 0,
+            allocations: 0,
+            allocated: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    // Allocations made while the thread is being torn down simply go uncounted.
+    let _ = COUNTERS.try_with(|counters| {
+        let mut value = counters.get();
+        f(&mut value);
+        counters.set(value);
+    });
+}
+
+fn record_alloc(size: usize) {
+    update(|c| {
+        c.current += size;
+        c.peak = c.peak.max(c.current);
+        c.allocations += 1;
+        c.allocated += size as u64;
+    });
+}
+
+fn record_dealloc(size: usize) {
+    // Memory from another thread can be freed here, so this thread's count can't go negative.
+    update(|c| c.current = c.current.saturating_sub(size));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// Heap use while running something.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Most bytes held at once, beyond what was held before.
+    pub peak_bytes: usize,
+    /// Allocations made, counting each reallocation as one.
+    pub allocations: u64,
+    /// Total bytes asked for over all allocations.
+    pub allocated_bytes: u64,
+}
+
+/// Runs `f`, returning its heap use on this thread if allocations are [counted](ENABLED).
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+
+    let before = COUNTERS.with(Cell::get);
+    update(|c| c.peak = c.current);
+
+    let result = f();
+
+    let after = COUNTERS.with(Cell::get);
+    // Keep the outer peak for anything measuring around this.
+    update(|c| c.peak = c.peak.max(before.peak));
+
+    let usage = MemoryUsage {
+        peak_bytes: after.peak.saturating_sub(before.current),
+        allocations: after.allocations - before.allocations,
+        allocated_bytes: after.allocated - before.allocated,
+    };
+
+    (result, Some(usage))
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+
+    #[test]
+    fn measures_only_when_enabled() {
+        let (total, usage) = measure(|| {
+            let buffer = std::hint::black_box(vec![1u8; 1 << 20]);
+            let small = std::hint::black_box(vec![0u64; 16]);
+            buffer.len() + small.len()
+        });
+
+        assert_eq!(total, (1 << 20) + 16);
+
+        assert_eq!(usage.is_some(), ENABLED);
+
+        if let Some(usage) = usage {
+            assert!(usage.peak_bytes >= (1 << 20) + 128);
+            assert!(usage.allocations >= 2);
+            assert!(usage.allocated_bytes >= usage.peak_bytes as u64);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::ParseError;
+
+/// Why a number couldn't be read, with the index of the offending byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberError {
+    MissingDigits { index: usize },
+    Overflow { index: usize },
+    TrailingBytes { index: usize },
+}
+
+impl NumberError {
+    pub fn index(self) -> usize {
+        match self {
+            Self::MissingDigits { index }
+            | Self::Overflow { index }
+            | Self::TrailingBytes { index } => index,
+        }
+    }
+
+    pub fn expected(self) -> &'static str {
+        match self {
+            Self::MissingDigits { .. } => "digit",
+            Self::Overflow { .. } => "number small enough to fit",
+            Self::TrailingBytes { .. } => "end of number",
+        }
+    }
+
+    /// Turns this into a [`ParseError`] for `slice`, the bytes that were given to the parser.
+    pub fn into_parse_error(self, day: u8, slice: &[u8]) -> ParseError {
+        ParseError::unexpected(day, slice, self.index(), self.expected())
+    }
+}
+
+/// An integer that can be read from ASCII decimal digits.
+pub trait Number: Copy {
+    /// Reads the number at the start of `bytes`, returning it and the number of bytes consumed.
+    ///
+    /// Signed types accept a single leading `-`.
+    fn parse_prefix(bytes: &[u8]) -> Result<(Self, usize), NumberError>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            #[inline]
+            fn parse_prefix(bytes: &[u8]) -> Result<(Self, usize), NumberError> {
+                let mut value: $t = 0;
+                let mut len = 0;
+
+                while let Some(&byte @ b'0'..=b'9') = bytes.get(len) {
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| v.checked_add((byte - b'0') as $t))
+                        .ok_or(NumberError::Overflow { index: len })?;
+                    len += 1;
+                }
+
+                if len == 0 {
+                    return Err(NumberError::MissingDigits { index: 0 });
+                }
+
+                Ok((value, len))
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            #[inline]
+            fn parse_prefix(bytes: &[u8]) -> Result<(Self, usize), NumberError> {
+                let negative = bytes.first() == Some(&b'-');
+                let start = negative as usize;
+
+                let mut value: $t = 0;
+                let mut len = start;
+
+                // Accumulating towards the sign lets `MIN` be parsed without overflowing.
+                while let Some(&byte @ b'0'..=b'9') = bytes.get(len) {
+                    let digit = (byte - b'0') as $t;
+
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| {
+                            if negative {
+                                v.checked_sub(digit)
+                            } else {
+                                v.checked_add(digit)
+                            }
+                        })
+                        .ok_or(NumberError::Overflow { index: len })?;
+                    len += 1;
+                }
+
+                if len == start {
+                    return Err(NumberError::MissingDigits { index: start });
+                }
+
+                Ok((value, len))
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Reads the number at the start of `bytes`, returning it and the number of bytes consumed.
+#[inline]
+pub fn number<T: Number>(bytes: &[u8]) -> Result<(T, usize), NumberError> {
+    T::parse_prefix(bytes)
+}
+
+/// Reads the number starting at `index` of `line`, returning it and the index just past it.
+#[inline]
+pub fn number_at<T: Number>(day: u8, line: &[u8], index: usize) -> Result<(T, usize), ParseError> {
+    let rest = line.get(index..).unwrap_or_default();
+
+    T::parse_prefix(rest)
+        .map(|(value, len)| (value, index + len))
+        .map_err(|e| ParseError::unexpected(day, line, index + e.index(), e.expected()))
+}
+
+/// Reads a number that has to span all of `bytes`.
+#[inline]
+pub fn exact<T: Number>(bytes: &[u8]) -> Result<T, NumberError> {
+    let (value, len) = T::parse_prefix(bytes)?;
+
+    if len != bytes.len() {
+        return Err(NumberError::TrailingBytes { index: len });
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned() {
+        assert_eq!(number::<u8>(b"255,"), Ok((255, 3)));
+        assert_eq!(number::<u64>(b"0012345678901"), Ok((12345678901, 13)));
+        assert_eq!(
+            number::<u8>(b"256"),
+            Err(NumberError::Overflow { index: 2 })
+        );
+        assert_eq!(
+            number::<u32>(b"-1"),
+            Err(NumberError::MissingDigits { index: 0 })
+        );
+    }
+
+    #[test]
+    fn signed() {
+        assert_eq!(number::<i8>(b"-128 "), Ok((-128, 4)));
+        assert_eq!(number::<i8>(b"127"), Ok((127, 3)));
+        assert_eq!(
+            number::<i8>(b"128"),
+            Err(NumberError::Overflow { index: 2 })
+        );
+        assert_eq!(
+            number::<i64>(b"-"),
+            Err(NumberError::MissingDigits { index: 1 })
+        );
+    }
+
+    #[test]
+    fn located_numbers() {
+        assert_eq!(number_at::<u32>(1, b"move 12 from", 5), Ok((12, 7)));
+
+        let error = number_at::<u8>(1, b"move 300", 5).unwrap_err();
+        assert_eq!((error.column, error.found), (8, Some('0')));
+    }
+
+    #[test]
+    fn exact_numbers() {
+        assert_eq!(exact::<u16>(b"1234"), Ok(1234));
+        assert_eq!(
+            exact::<u16>(b"12a4"),
+            Err(NumberError::TrailingBytes { index: 2 })
+        );
+        assert_eq!(
+            exact::<u16>(b""),
+            Err(NumberError::MissingDigits { index: 0 })
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use super::{check_day, CASES};
+
+fn totals(input: &str) -> Vec<i64> {
+    input
+        .split("\n\n")
+        .map(|elf| elf.lines().map(|item| item.parse::<i64>().unwrap()).sum())
+        .collect()
+}
+
+fn part1(input: &str) -> String {
+    totals(input).into_iter().max().unwrap().to_string()
+}
+
+fn part2(input: &str) -> String {
+    let mut totals = totals(input);
+    totals.sort();
+    totals.iter().rev().take(3).sum::<i64>().to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(1, CASES, 3..=12, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,42 @@
+use super::{check_day, CASES};
+
+/// Each round as the opponent's shape and the second column, both as 0, 1 or 2.
+fn rounds(input: &str) -> impl Iterator<Item = (i64, i64)> + '_ {
+    input.lines().map(|line| {
+        let line = line.as_bytes();
+        ((line[0] - b'A') as i64, (line[2] - b'X') as i64)
+    })
+}
+
+/// Scores a round where shapes are 0 for rock, 1 for paper and 2 for scissors.
+fn score(opponent: i64, player: i64) -> i64 {
+    let outcome = match (player - opponent).rem_euclid(3) {
+        0 => 3,
+        1 => 6,
+        _ => 0,
+    };
+
+    player + 1 + outcome
+}
+
+fn part1(input: &str) -> String {
+    let total: i64 = rounds(input).map(|(a, x)| score(a, x)).sum();
+    total.to_string()
+}
+
+fn part2(input: &str) -> String {
+    let total: i64 = rounds(input)
+        .map(|(a, x)| {
+            // X loses, Y draws and Z wins, so the shape is one back, the same or one ahead.
+            let player = (a + x - 1).rem_euclid(3);
+            score(a, player)
+        })
+        .sum();
+
+    total.to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(2, CASES, 1..=50, &[(1, part1), (2, part2)]);
+}
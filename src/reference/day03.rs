@@ -0,0 +1,42 @@
+use super::{check_day, CASES};
+
+fn priority(item: char) -> i64 {
+    match item {
+        'a'..='z' => item as i64 - 'a' as i64 + 1,
+        _ => item as i64 - 'A' as i64 + 27,
+    }
+}
+
+fn part1(input: &str) -> String {
+    let total: i64 = input
+        .lines()
+        .map(|line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            let shared = left.chars().find(|&c| right.contains(c)).unwrap();
+            priority(shared)
+        })
+        .sum();
+
+    total.to_string()
+}
+
+fn part2(input: &str) -> String {
+    let lines = input.lines().collect::<Vec<_>>();
+    let total: i64 = lines
+        .chunks(3)
+        .map(|group| {
+            let badge = group[0]
+                .chars()
+                .find(|&c| group[1].contains(c) && group[2].contains(c))
+                .unwrap();
+            priority(badge)
+        })
+        .sum();
+
+    total.to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(3, CASES, 1..=5, &[(1, part1), (2, part2)]);
+}
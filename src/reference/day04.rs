@@ -0,0 +1,29 @@
+use super::{check_day, CASES};
+
+fn pairs(input: &str) -> impl Iterator<Item = [u32; 4]> + '_ {
+    input.lines().map(|line| {
+        let mut numbers = line.split([',', '-']).map(|n| n.parse().unwrap());
+        [(); 4].map(|_| numbers.next().unwrap())
+    })
+}
+
+fn part1(input: &str) -> String {
+    let count = pairs(input)
+        .filter(|&[a, b, c, d]| (a <= c && d <= b) || (c <= a && b <= d))
+        .count();
+
+    count.to_string()
+}
+
+fn part2(input: &str) -> String {
+    let count = pairs(input)
+        .filter(|&[a, b, c, d]| a <= d && c <= b)
+        .count();
+
+    count.to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(4, CASES, 1..=50, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,66 @@
+use super::{check_day, CASES};
+
+/// The stacks listed bottom to top, and the moves as count, source and target stack indices.
+fn procedure(input: &str) -> (Vec<Vec<char>>, Vec<[usize; 3]>) {
+    let (drawing, moves) = input.split_once("\n\n").unwrap();
+    let mut rows = drawing.lines().rev();
+
+    let count = rows.next().unwrap().split_whitespace().count();
+    let mut stacks = vec![Vec::new(); count];
+
+    for row in rows {
+        let row = row.chars().collect::<Vec<_>>();
+
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            match row.get(i * 4 + 1) {
+                Some(' ') | None => {}
+                Some(&item) => stack.push(item),
+            }
+        }
+    }
+
+    let moves = moves
+        .lines()
+        .map(|line| {
+            let words = line.split(' ').collect::<Vec<_>>();
+            let number = |i: usize| words[i].parse::<usize>().unwrap();
+            [number(1), number(3) - 1, number(5) - 1]
+        })
+        .collect();
+
+    (stacks, moves)
+}
+
+fn tops(stacks: &[Vec<char>]) -> String {
+    stacks.iter().map(|stack| stack.last().unwrap()).collect()
+}
+
+fn part1(input: &str) -> String {
+    let (mut stacks, moves) = procedure(input);
+
+    for [count, from, to] in moves {
+        for _ in 0..count {
+            let item = stacks[from].pop().unwrap();
+            stacks[to].push(item);
+        }
+    }
+
+    tops(&stacks)
+}
+
+fn part2(input: &str) -> String {
+    let (mut stacks, moves) = procedure(input);
+
+    for [count, from, to] in moves {
+        let split = stacks[from].len() - count;
+        let items = stacks[from].split_off(split);
+        stacks[to].extend(items);
+    }
+
+    tops(&stacks)
+}
+
+#[test]
+fn matches_reference() {
+    check_day(5, CASES, 1..=30, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+use super::{check_day, CASES};
+
+fn marker_end(input: &str, length: usize) -> String {
+    let signal = input.as_bytes();
+    let end = (length..=signal.len())
+        .find(|&end| {
+            signal[end - length..end]
+                .iter()
+                .collect::<HashSet<_>>()
+                .len()
+                == length
+        })
+        .unwrap();
+
+    end.to_string()
+}
+
+fn part1(input: &str) -> String {
+    marker_end(input, 4)
+}
+
+fn part2(input: &str) -> String {
+    marker_end(input, 14)
+}
+
+#[test]
+fn matches_reference() {
+    check_day(6, CASES, 0..=60, &[(1, part1), (2, part2)]);
+}
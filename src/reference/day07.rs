@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use super::{check_day, CASES};
+
+/// The total size of every directory, keyed by its path.
+fn directory_sizes(input: &str) -> HashMap<Vec<&str>, i64> {
+    let mut path = Vec::new();
+    let mut sizes = HashMap::new();
+
+    for line in input.lines() {
+        let words = line.split(' ').collect::<Vec<_>>();
+
+        match words[..] {
+            ["$", "cd", "/"] => {
+                path.clear();
+                sizes.entry(Vec::new()).or_insert(0);
+            }
+            ["$", "cd", ".."] => {
+                path.pop();
+            }
+            ["$", "cd", name] => path.push(name),
+            ["$", "ls"] => {}
+            // Empty directories count too, with a size of zero.
+            ["dir", name] => {
+                let mut dir = path.clone();
+                dir.push(name);
+                sizes.entry(dir).or_insert(0);
+            }
+            [size, _] => {
+                let size = size.parse::<i64>().unwrap();
+
+                for end in 0..=path.len() {
+                    *sizes.entry(path[..end].to_vec()).or_insert(0) += size;
+                }
+            }
+            _ => panic!("unexpected line {line:?}"),
+        }
+    }
+
+    sizes
+}
+
+fn part1(input: &str) -> String {
+    let sizes = directory_sizes(input);
+    let total: i64 = sizes.values().filter(|&&size| size <= 100_000).sum();
+
+    total.to_string()
+}
+
+fn part2(input: &str) -> String {
+    let sizes = directory_sizes(input);
+    let used = sizes[&Vec::new()];
+    let needed = 30_000_000 - (70_000_000 - used);
+
+    sizes
+        .values()
+        .filter(|&&size| size >= needed)
+        .min()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(7, CASES, 0..=30, &[(1, part1), (2, part2)]);
+}
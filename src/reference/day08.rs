@@ -0,0 +1,62 @@
+use super::{check_day, CASES};
+
+fn forest(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+/// The trees seen looking from `(x, y)` in each direction, nearest first.
+fn sight_lines(forest: &[Vec<u8>], x: usize, y: usize) -> [Vec<u8>; 4] {
+    let row = &forest[y];
+    let column = forest.iter().map(|row| row[x]).collect::<Vec<_>>();
+
+    [
+        row[..x].iter().rev().copied().collect(),
+        row[x + 1..].to_vec(),
+        column[..y].iter().rev().copied().collect(),
+        column[y + 1..].to_vec(),
+    ]
+}
+
+fn part1(input: &str) -> String {
+    let forest = forest(input);
+    let mut visible = 0;
+
+    for (y, row) in forest.iter().enumerate() {
+        for (x, &tree) in row.iter().enumerate() {
+            if sight_lines(&forest, x, y)
+                .iter()
+                .any(|line| line.iter().all(|&other| other < tree))
+            {
+                visible += 1;
+            }
+        }
+    }
+
+    visible.to_string()
+}
+
+fn part2(input: &str) -> String {
+    let forest = forest(input);
+    let mut best = 0;
+
+    for (y, row) in forest.iter().enumerate() {
+        for (x, &tree) in row.iter().enumerate() {
+            let score: usize = sight_lines(&forest, x, y)
+                .iter()
+                .map(|line| match line.iter().position(|&other| other >= tree) {
+                    Some(blocker) => blocker + 1,
+                    None => line.len(),
+                })
+                .product();
+
+            best = best.max(score);
+        }
+    }
+
+    best.to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(8, CASES, 1..=144, &[(1, part1), (2, part2)]);
+}
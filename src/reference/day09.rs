@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+
+use super::{check_day, CASES};
+
+fn tail_positions(input: &str, knots: usize) -> String {
+    let mut rope = vec![(0i32, 0i32); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+
+    for line in input.lines() {
+        let (direction, count) = line.split_once(' ').unwrap();
+        let (dx, dy) = match direction {
+            "L" => (-1, 0),
+            "R" => (1, 0),
+            "U" => (0, -1),
+            _ => (0, 1),
+        };
+
+        for _ in 0..count.parse::<u32>().unwrap() {
+            rope[0].0 += dx;
+            rope[0].1 += dy;
+
+            for i in 1..knots {
+                let (x, y) = (rope[i - 1].0 - rope[i].0, rope[i - 1].1 - rope[i].1);
+
+                if x.abs() > 1 || y.abs() > 1 {
+                    rope[i].0 += x.signum();
+                    rope[i].1 += y.signum();
+                }
+            }
+
+            visited.insert(rope[knots - 1]);
+        }
+    }
+
+    visited.len().to_string()
+}
+
+fn part1(input: &str) -> String {
+    tail_positions(input, 2)
+}
+
+fn part2(input: &str) -> String {
+    tail_positions(input, 10)
+}
+
+#[test]
+fn matches_reference() {
+    check_day(9, CASES, 1..=60, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,52 @@
+use super::{check_day, CASES};
+
+/// The value of the register during each cycle.
+fn register(input: &str) -> Vec<i64> {
+    let mut x = 1;
+    let mut cycles = Vec::new();
+
+    for line in input.lines() {
+        cycles.push(x);
+
+        if let Some(value) = line.strip_prefix("addx ") {
+            cycles.push(x);
+            x += value.parse::<i64>().unwrap();
+        }
+    }
+
+    cycles
+}
+
+fn part1(input: &str) -> String {
+    let cycles = register(input);
+    let strength: i64 = [20, 60, 100, 140, 180, 220]
+        .iter()
+        .map(|&cycle| cycle as i64 * cycles[cycle - 1])
+        .sum();
+
+    strength.to_string()
+}
+
+fn part2(input: &str) -> String {
+    let cycles = register(input);
+    let rows = cycles[..240]
+        .chunks(40)
+        .map(|row| {
+            let pixels =
+                row.iter()
+                    .enumerate()
+                    .map(|(column, &x)| match (x - column as i64).abs() <= 1 {
+                        true => '#',
+                        false => '.',
+                    });
+            pixels.collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    rows.join("\n")
+}
+
+#[test]
+fn matches_reference() {
+    check_day(10, CASES, 240..=240, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,87 @@
+use super::check_day;
+
+/// Each part plays thousands of rounds, so fewer cases are checked.
+const CASES: u64 = 50;
+
+struct Monkey {
+    items: Vec<u64>,
+    operation: Vec<String>,
+    divisor: u64,
+    targets: [usize; 2],
+}
+
+fn last_number(line: &str) -> u64 {
+    line.rsplit(' ').next().unwrap().parse().unwrap()
+}
+
+fn monkeys(input: &str) -> Vec<Monkey> {
+    input
+        .split("\n\n")
+        .map(|monkey| {
+            let lines = monkey.lines().collect::<Vec<_>>();
+            let (_, items) = lines[1].split_once(": ").unwrap();
+            let (_, operation) = lines[2].split_once("= ").unwrap();
+
+            Monkey {
+                items: items.split(", ").map(|i| i.parse().unwrap()).collect(),
+                operation: operation.split(' ').map(str::to_owned).collect(),
+                divisor: last_number(lines[3]),
+                targets: [
+                    last_number(lines[4]) as usize,
+                    last_number(lines[5]) as usize,
+                ],
+            }
+        })
+        .collect()
+}
+
+/// Plays the rounds and multiplies the two highest inspection counts.
+///
+/// Worry levels are kept modulo every divisor times the relief divisor, which leaves both the
+/// division and every test unchanged while keeping the numbers small.
+fn monkey_business(input: &str, rounds: usize, relief: u64) -> String {
+    let mut monkeys = monkeys(input);
+    let modulus = monkeys.iter().map(|m| m.divisor as u128).product::<u128>() * relief as u128;
+    let mut inspections = vec![0u64; monkeys.len()];
+
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            for item in std::mem::take(&mut monkeys[i].items) {
+                let operand = |word: &str| match word {
+                    "old" => item as u128,
+                    number => number.parse().unwrap(),
+                };
+
+                let operation = &monkeys[i].operation;
+                let (left, right) = (operand(&operation[0]), operand(&operation[2]));
+                let worry = match operation[1].as_str() {
+                    "+" => left + right,
+                    _ => left * right,
+                } % modulus
+                    / relief as u128;
+                let worry = worry as u64;
+
+                let target = monkeys[i].targets[!worry.is_multiple_of(monkeys[i].divisor) as usize];
+                monkeys[target].items.push(worry);
+                inspections[i] += 1;
+            }
+        }
+    }
+
+    inspections.sort();
+    inspections.reverse();
+    (inspections[0] * inspections[1]).to_string()
+}
+
+fn part1(input: &str) -> String {
+    monkey_business(input, 20, 3)
+}
+
+fn part2(input: &str) -> String {
+    monkey_business(input, 10_000, 1)
+}
+
+#[test]
+fn matches_reference() {
+    check_day(11, CASES, 1..=5, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+
+use super::check_day;
+
+/// Both parts search the whole map, so fewer cases are checked.
+const CASES: u64 = 500;
+
+/// Fewest steps from any of `starts` to the goal, climbing at most one level per step.
+fn shortest_path(input: &str, is_start: fn(u8) -> bool) -> Option<usize> {
+    let map = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let height = |b: u8| match b {
+        b'S' => b'a',
+        b'E' => b'z',
+        b => b,
+    };
+
+    let mut steps = vec![vec![None; map[0].len()]; map.len()];
+    let mut queue = VecDeque::new();
+
+    for (y, row) in map.iter().enumerate() {
+        for (x, &b) in row.iter().enumerate() {
+            if is_start(b) {
+                steps[y][x] = Some(0);
+                queue.push_back((x, y));
+            }
+        }
+    }
+
+    while let Some((x, y)) = queue.pop_front() {
+        let here = steps[y][x].unwrap();
+
+        if map[y][x] == b'E' {
+            return Some(here);
+        }
+
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+
+        for (nx, ny) in neighbours {
+            let Some(&b) = map.get(ny).and_then(|row| row.get(nx)) else {
+                continue;
+            };
+
+            if height(b) <= height(map[y][x]) + 1 && steps[ny][nx].is_none() {
+                steps[ny][nx] = Some(here + 1);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    None
+}
+
+fn part1(input: &str) -> String {
+    shortest_path(input, |b| b == b'S').unwrap().to_string()
+}
+
+fn part2(input: &str) -> String {
+    shortest_path(input, |b| matches!(b, b'S' | b'a'))
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(12, CASES, 0..=300, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,66 @@
+use super::{check_day, CASES};
+use crate::gen::day13::Packet;
+
+fn parse(text: &str) -> Packet {
+    let (packet, rest) = parse_prefix(text);
+    assert!(rest.is_empty(), "trailing {rest:?}");
+    packet
+}
+
+fn parse_prefix(text: &str) -> (Packet, &str) {
+    let Some(mut rest) = text.strip_prefix('[') else {
+        let end = text.find([',', ']']).unwrap_or(text.len());
+        return (Packet::Int(text[..end].parse().unwrap()), &text[end..]);
+    };
+
+    let mut items = Vec::new();
+
+    while let Some(after) = rest.strip_prefix(',').or(Some(rest)) {
+        if let Some(after) = after.strip_prefix(']') {
+            return (Packet::List(items), after);
+        }
+
+        let (item, after) = parse_prefix(after);
+        items.push(item);
+        rest = after;
+    }
+
+    unreachable!()
+}
+
+fn packets(input: &str) -> Vec<Packet> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(parse)
+        .collect()
+}
+
+fn part1(input: &str) -> String {
+    let sum: usize = packets(input)
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] < pair[1])
+        .map(|(i, _)| i + 1)
+        .sum();
+
+    sum.to_string()
+}
+
+fn part2(input: &str) -> String {
+    let mut packets = packets(input);
+    packets.extend(Packet::dividers());
+    packets.sort();
+
+    let key: usize = Packet::dividers()
+        .iter()
+        .map(|divider| packets.iter().position(|p| p == divider).unwrap() + 1)
+        .product();
+
+    key.to_string()
+}
+
+#[test]
+fn matches_reference() {
+    check_day(13, CASES, 1..=10, &[(1, part1), (2, part2)]);
+}
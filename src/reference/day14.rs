@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use super::check_day;
+
+/// The floor in part 2 makes for piles of thousands of grains, so fewer cases are checked.
+const CASES: u64 = 200;
+
+const SOURCE: (i32, i32) = (500, 0);
+
+fn rocks(input: &str) -> HashSet<(i32, i32)> {
+    let mut rocks = HashSet::new();
+
+    for path in input.lines() {
+        let vertices = path
+            .split(" -> ")
+            .map(|vertex| {
+                let (x, y) = vertex.split_once(',').unwrap();
+                (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        for pair in vertices.windows(2) {
+            let [(x1, y1), (x2, y2)] = [pair[0], pair[1]];
+
+            for x in x1.min(x2)..=x1.max(x2) {
+                for y in y1.min(y2)..=y1.max(y2) {
+                    rocks.insert((x, y));
+                }
+            }
+        }
+
+        rocks.insert(vertices[0]);
+    }
+
+    rocks
+}
+
+/// Drops grains from the source until one passes `floor - 1` or the source is blocked.
+///
+/// With `solid_floor` grains come to rest on the floor instead of falling past it.
+fn pour(input: &str, solid_floor: bool) -> String {
+    let mut blocked = rocks(input);
+    let floor = blocked.iter().map(|&(_, y)| y).max().unwrap() + 2;
+    let mut grains = 0;
+
+    while !blocked.contains(&SOURCE) {
+        let (mut x, mut y) = SOURCE;
+
+        loop {
+            if y + 1 == floor {
+                break;
+            }
+
+            match [x, x - 1, x + 1]
+                .into_iter()
+                .find(|&next| !blocked.contains(&(next, y + 1)))
+            {
+                Some(next) => (x, y) = (next, y + 1),
+                None => break,
+            }
+        }
+
+        if y + 1 == floor && !solid_floor {
+            return grains.to_string();
+        }
+
+        blocked.insert((x, y));
+        grains += 1;
+    }
+
+    grains.to_string()
+}
+
+fn part1(input: &str) -> String {
+    pour(input, false)
+}
+
+fn part2(input: &str) -> String {
+    pour(input, true)
+}
+
+#[test]
+fn matches_reference() {
+    check_day(14, CASES, 1..=6, &[(1, part1), (2, part2)]);
+}
@@ -0,0 +1,136 @@
+use super::SEED;
+use crate::{day15, rng::Rng};
+
+/// Far fewer positions than the puzzle's, so both parts can be brute forced.
+const SIZE: i64 = 20;
+const CASES: u64 = 500;
+
+type Sensor = ([i64; 2], [i64; 2]);
+
+fn distance([x1, y1]: [i64; 2], [x2, y2]: [i64; 2]) -> i64 {
+    (x1 - x2).abs() + (y1 - y2).abs()
+}
+
+fn covered(sensors: &[Sensor], point: [i64; 2]) -> bool {
+    sensors
+        .iter()
+        .any(|&(sensor, beacon)| distance(sensor, point) <= distance(sensor, beacon))
+}
+
+fn sensors(input: &str) -> Vec<Sensor> {
+    input
+        .lines()
+        .map(|line| {
+            let numbers = line
+                .split(|c: char| c != '-' && !c.is_ascii_digit())
+                .filter(|n| !n.is_empty())
+                .map(|n| n.parse().unwrap())
+                .collect::<Vec<_>>();
+
+            ([numbers[0], numbers[1]], [numbers[2], numbers[3]])
+        })
+        .collect()
+}
+
+fn part1(input: &str, row: i64) -> i64 {
+    let sensors = sensors(input);
+    let reach = sensors.iter().map(|&(s, b)| distance(s, b)).max().unwrap();
+    let xs = sensors.iter().map(|(s, _)| s[0]);
+    let (min, max) = (xs.clone().min().unwrap() - reach, xs.max().unwrap() + reach);
+
+    (min..=max)
+        .filter(|&x| covered(&sensors, [x, row]))
+        .filter(|&x| sensors.iter().all(|&(_, beacon)| beacon != [x, row]))
+        .count() as i64
+}
+
+fn part2(input: &str) -> Option<i64> {
+    let sensors = sensors(input);
+
+    (0..=SIZE)
+        .flat_map(|x| (0..=SIZE).map(move |y| [x, y]))
+        .find(|&point| !covered(&sensors, point))
+        .map(|[x, y]| x * 4_000_000 + y)
+}
+
+fn format(sensors: &[Sensor]) -> String {
+    let lines = sensors
+        .iter()
+        .map(|([sx, sy], [bx, by])| {
+            format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}")
+        })
+        .collect::<Vec<_>>();
+
+    lines.join("\n")
+}
+
+/// A beacon exactly `radius` away from `sensor`.
+fn beacon_at(rng: &mut Rng, [x, y]: [i64; 2], radius: i64) -> [i64; 2] {
+    let dx = rng.range(-radius..=radius);
+    let dy = radius - dx.abs();
+
+    [x + dx, if rng.chance(0.5) { y + dy } else { y - dy }]
+}
+
+/// Sensors scattered around the search area, for counting along a row.
+fn random_sensors(rng: &mut Rng) -> String {
+    let sensors = (0..rng.range(1..=8))
+        .map(|_| {
+            let sensor = [rng.range(-5..=2 * SIZE), rng.range(-5..=2 * SIZE)];
+            let radius = rng.range(0..=10);
+            (sensor, beacon_at(rng, sensor, radius))
+        })
+        .collect::<Vec<_>>();
+
+    format(&sensors)
+}
+
+/// Sensors covering the whole search area but for one position away from its edges.
+///
+/// Each sensor is placed on a position that's still uncovered, reaching as far as it can
+/// without covering the gap.
+fn random_gap(rng: &mut Rng) -> String {
+    let gap = [rng.range(1..=SIZE - 1), rng.range(1..=SIZE - 1)];
+    let mut sensors = Vec::new();
+
+    loop {
+        let uncovered = (0..=SIZE)
+            .flat_map(|x| (0..=SIZE).map(move |y| [x, y]))
+            .filter(|&point| point != gap && !covered(&sensors, point))
+            .collect::<Vec<_>>();
+
+        if uncovered.is_empty() {
+            return format(&sensors);
+        }
+
+        let sensor = *rng.pick(&uncovered);
+        let radius = distance(sensor, gap) - 1;
+        sensors.push((sensor, beacon_at(rng, sensor, radius)));
+    }
+}
+
+#[test]
+fn matches_reference() {
+    for case in 0..CASES {
+        let mut rng = Rng::new(SEED + case);
+
+        let input = random_sensors(&mut rng);
+        let sensors = day15::parse_sensors(input.as_bytes()).unwrap();
+        let row = rng.range(-5..=2 * SIZE);
+
+        assert_eq!(
+            day15::excluded_positions(&sensors, row),
+            part1(&input, row),
+            "day15 part1 on row {row} disagrees with\n{input}"
+        );
+
+        let input = random_gap(&mut rng);
+        let sensors = day15::parse_sensors(input.as_bytes()).unwrap();
+
+        assert_eq!(
+            day15::find_distress_beacon(&sensors, SIZE).map(day15::tuning_frequency),
+            part2(&input),
+            "day15 part2 disagrees with\n{input}"
+        );
+    }
+}
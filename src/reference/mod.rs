@@ -0,0 +1,59 @@
+//! Straightforward solutions for every day, written for clarity rather than speed.
+//!
+//! Each day's property test checks that the registered solvers agree with the reference on
+//! thousands of small inputs from its [generator](crate::gen).
+
+use std::ops::RangeInclusive;
+
+use crate::{gen, registry, rng::Rng};
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+
+/// How many random inputs each day is checked on, unless its solvers are slow.
+const CASES: u64 = 2000;
+
+/// Case `n` is generated from seed `SEED + n`, so a failing case can be replayed on its own.
+const SEED: u64 = 2022;
+
+type Part = fn(&str) -> String;
+
+/// Checks every registered solver of `day` against the reference `parts` on `cases` generated
+/// inputs, with sizes picked from `sizes`.
+///
+/// Panics with the input on the first disagreement.
+fn check_day(day: u8, cases: u64, sizes: RangeInclusive<usize>, parts: &[(u8, Part)]) {
+    let generator = gen::find(day).expect("day has a generator");
+
+    for case in 0..cases {
+        let mut rng = Rng::new(SEED + case);
+        let size = sizes.start() + rng.below(sizes.end() - sizes.start() + 1);
+        let input = generator(&mut rng, size);
+
+        for &(part, reference) in parts {
+            let expected = reference(&input);
+
+            for solver in registry::find(Some(day), Some(part), None) {
+                let label = format!("day{day} part{part} {} on case {case}", solver.variant);
+                let answer = solver
+                    .run(&input)
+                    .unwrap_or_else(|e| panic!("{label} failed: {e}\n{input}"));
+
+                assert_eq!(answer, expected, "{label} disagrees with\n{input}");
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::{any::Any, error::Error, fmt::Display};
+
+use crate::{solution::Solution, *};
+
+pub type SolverError = Box<dyn Error + Send + Sync>;
+
+/// A day's parsed input, as produced by [`Solution::parse`].
+pub type Model = Box<dyn Any>;
+
+/// A solution function, type-erased so every day can be called the same way.
+///
+/// Every solver of a day shares the same `parse` step, so its model can be reused across parts.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    pub variant: &'static str,
+    pub name: Option<&'static str>,
+    pub parse: fn(&str) -> Result<Model, SolverError>,
+    pub solve: fn(&dyn Any) -> Result<String, SolverError>,
+}
+
+impl Solver {
+    /// Parses `input` and solves it.
+    pub fn run(&self, input: &str) -> Result<String, SolverError> {
+        (self.solve)(&*(self.parse)(input)?)
+    }
+}
+
+/// Name given to solvers registered without a variant.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Output types returned by the parts of a [`Solution`].
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<String, SolverError>;
+}
+
+macro_rules! impl_into_answer {
+    ($($t:ty),*) => {$(
+        impl IntoAnswer for $t {
+            fn into_answer(self) -> Result<String, SolverError> {
+                Ok(self.to_string())
+            }
+        }
+    )*};
+}
+
+impl_into_answer!(i32, i64, u32, u64, usize, String);
+
+impl<T, E> IntoAnswer for Result<T, E>
+where
+    T: Display,
+    E: Into<SolverError>,
+{
+    fn into_answer(self) -> Result<String, SolverError> {
+        self.map(|answer| answer.to_string()).map_err(Into::into)
+    }
+}
+
+/// Parses input for `S`, boxing its model.
+fn parse_model<S: Solution>(input: &str) -> Result<Model, SolverError> {
+    S::parse(input)
+        .map(|model| Box::new(model) as Model)
+        .map_err(Into::into)
+}
+
+fn downcast<S: Solution>(model: &dyn Any) -> &S::Input {
+    model
+        .downcast_ref()
+        .expect("model parsed by another day's solution")
+}
+
+fn solve_part1<S: Solution>(model: &dyn Any) -> Result<String, SolverError> {
+    S::part1(downcast::<S>(model)).into_answer()
+}
+
+fn solve_part2<S: Solution>(model: &dyn Any) -> Result<String, SolverError> {
+    S::part2(downcast::<S>(model)).into_answer()
+}
+
+macro_rules! solvers {
+    ($($solution:path $(as $variant:ident)?;)*) => {
+        /// Every solver in the crate, ordered by day and part.
+        pub static SOLVERS: &[Solver] = &[$(
+            solvers!(@solver $solution, 1, solve_part1, $($variant)?),
+            solvers!(@solver $solution, 2, solve_part2, $($variant)?),
+        )*];
+    };
+
+    (@solver $solution:path, $part:literal, $solve:ident, $($variant:ident)?) => {
+        Solver {
+            day: <$solution as Solution>::DAY,
+            part: $part,
+            variant: solvers!(@variant $($variant)?),
+            name: <$solution as Solution>::NAME,
+            parse: parse_model::<$solution>,
+            solve: $solve::<$solution>,
+        }
+    };
+
+    (@variant) => { DEFAULT_VARIANT };
+    (@variant $variant:ident) => { stringify!($variant) };
+}
+
+solvers! {
+    day01::Day01;
+    day02::Day02;
+    day03::Day03;
+    day04::Day04;
+    day05::Day05;
+    day06::Day06 as naive;
+    day07::Day07;
+    day08::Day08;
+    day09::Day09;
+    day10::Day10;
+    day11::Day11;
+    day12::Day12;
+    day13::Day13;
+    day14::Day14;
+    day15::Day15;
+}
+
+/// The solvers matching the given filters, where `None` matches anything.
+pub fn find(
+    day: Option<u8>,
+    part: Option<u8>,
+    variant: Option<&str>,
+) -> impl Iterator<Item = &'static Solver> + '_ {
+    SOLVERS.iter().filter(move |s| {
+        day.is_none_or(|d| s.day == d)
+            && part.is_none_or(|p| s.part == p)
+            && variant.is_none_or(|v| s.variant == v)
+    })
+}
+
+/// Solves `part` of `day`, using its first registered variant.
+///
+/// `input` is taken as is, see [`normalize`](crate::input::normalize) for raw puzzle files.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, SolverError> {
+    let solver = find(Some(day), Some(part), None)
+        .next()
+        .ok_or_else(|| format!("no solver for day {day} part {part}"))?;
+
+    solver.run(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_solvers() {
+        assert_eq!(find(Some(6), None, None).count(), 2);
+        assert_eq!(find(Some(6), Some(1), Some("naive")).count(), 1);
+        assert_eq!(find(Some(6), None, Some(DEFAULT_VARIANT)).count(), 0);
+        assert_eq!(find(None, Some(1), None).count(), 15);
+    }
+
+    #[test]
+    fn runs_solvers() {
+        let solver = find(Some(1), Some(1), None).next().unwrap();
+
+        assert_eq!(solver.run("1\n2\n\n4").unwrap(), "4");
+        assert!(solver.run("1\nx").is_err());
+
+        let model = (solver.parse)("1\n2\n\n4\n\n5").unwrap();
+        let part2 = find(Some(1), Some(2), None).next().unwrap();
+        assert_eq!((solver.solve)(&*model).unwrap(), "5");
+        assert_eq!((part2.solve)(&*model).unwrap(), "12");
+    }
+
+    #[test]
+    fn solves_by_day() {
+        assert_eq!(solve(1, 2, "1\n\n2\n\n3\n\n4").unwrap(), "9");
+        assert_eq!(solve(6, 1, "abcd").unwrap(), "4");
+        assert!(solve(1, 3, "1").is_err());
+        assert!(solve(25, 1, "").is_err());
+    }
+}
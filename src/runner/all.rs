@@ -0,0 +1,311 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+    vec,
+};
+
+use super::{run_solvers, selected_inputs, Args, NamedInput, Outcome, DEFAULT_INPUT};
+use crate::registry::{self, Solver};
+
+/// Why a job gave no result.
+#[derive(Debug, PartialEq, Eq)]
+enum Failure {
+    Panicked(String),
+    TimedOut,
+}
+
+/// A job's result, with how long it ran for.
+type JobResult<T> = (Result<T, Failure>, Duration);
+
+enum Event<T> {
+    Started(usize, Instant),
+    Finished(usize, Result<T, Failure>, Duration),
+}
+
+type Queue<J> = Mutex<vec::IntoIter<(usize, J)>>;
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => (*message).to_owned(),
+        (_, Some(message)) => message.clone(),
+        _ => "panicked".to_owned(),
+    }
+}
+
+fn worker<J, T>(jobs: &Queue<J>, events: &Sender<Event<T>>, run: fn(&J) -> T) {
+    loop {
+        let Some((id, job)) = jobs.lock().expect("queue lock").next() else {
+            return;
+        };
+
+        let start = Instant::now();
+        if events.send(Event::Started(id, start)).is_err() {
+            return;
+        }
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| run(&job)))
+            .map_err(|payload| Failure::Panicked(panic_message(&*payload)));
+
+        if events
+            .send(Event::Finished(id, result, start.elapsed()))
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Runs `run` on each job over `workers` threads, catching panics.
+///
+/// A job still running after `timeout` is reported as timed out and its thread is left
+/// behind, with a fresh worker taking its place. Results are in job order.
+fn run_isolated<J, T>(
+    jobs: Vec<J>,
+    workers: usize,
+    timeout: Option<Duration>,
+    run: fn(&J) -> T,
+) -> Vec<JobResult<T>>
+where
+    J: Send + 'static,
+    T: Send + 'static,
+{
+    let mut results = jobs.iter().map(|_| None).collect::<Vec<_>>();
+    let mut remaining = jobs.len();
+
+    let queue = Arc::new(Mutex::new(
+        jobs.into_iter().enumerate().collect::<Vec<_>>().into_iter(),
+    ));
+    let (sender, events) = mpsc::channel();
+
+    let spawn_worker = || {
+        let (queue, sender) = (Arc::clone(&queue), sender.clone());
+        thread::spawn(move || worker(&queue, &sender, run));
+    };
+
+    for _ in 0..workers.clamp(1, remaining.max(1)) {
+        spawn_worker();
+    }
+
+    let mut running = HashMap::<usize, Instant>::new();
+
+    while remaining > 0 {
+        let deadline = timeout
+            .zip(running.values().min())
+            .map(|(t, &start)| start + t);
+
+        let event = match deadline {
+            Some(deadline) => {
+                events.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(Event::Started(id, start)) => {
+                running.insert(id, start);
+            }
+            Ok(Event::Finished(id, result, elapsed)) => {
+                // Jobs that already timed out are done with.
+                if running.remove(&id).is_some() {
+                    results[id] = Some((result, elapsed));
+                    remaining -= 1;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let (&id, _) = running
+                    .iter()
+                    .min_by_key(|&(_, start)| start)
+                    .expect("a deadline means a job is running");
+
+                running.remove(&id);
+                results[id] = Some((Err(Failure::TimedOut), timeout.unwrap_or_default()));
+                remaining -= 1;
+                spawn_worker();
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("a sender is kept for new workers"),
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every job finished or timed out"))
+        .collect()
+}
+
+/// Every solver of one day, on one of its inputs.
+struct Job {
+    day: u8,
+    solvers: Vec<&'static Solver>,
+    input: NamedInput,
+}
+
+fn run_job(job: &Job) -> Result<Vec<Outcome>, String> {
+    let text = job
+        .input
+        .load()
+        .map_err(|e| format!("can't read {}: {e}", job.input.path.display()))?;
+
+    Ok(run_solvers(&job.solvers, &text))
+}
+
+/// Multi-line answers like rendered letters are summarised rather than printed.
+fn short_answer(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_owned(),
+        lines => format!("({lines} lines)"),
+    }
+}
+
+/// Status and details of a job for the summary table, and whether it succeeded.
+fn describe(
+    result: &Result<Result<Vec<Outcome>, String>, Failure>,
+) -> (&'static str, String, bool) {
+    match result {
+        Ok(Ok(outcomes)) => match outcomes.iter().find_map(|o| o.answer.as_ref().err()) {
+            Some(error) => ("error", error.to_string(), false),
+            None => {
+                let answers = outcomes
+                    .iter()
+                    .filter_map(|o| o.answer.as_deref().ok())
+                    .map(short_answer)
+                    .collect::<Vec<_>>();
+
+                ("ok", answers.join(", "), true)
+            }
+        },
+        Ok(Err(error)) => ("error", error.clone(), false),
+        Err(Failure::Panicked(message)) => ("panicked", message.clone(), false),
+        Err(Failure::TimedOut) => ("timed out", String::new(), false),
+    }
+}
+
+/// Runs every day on a pool of worker threads and prints a summary table.
+pub(super) fn all(args: &Args) -> Result<bool, String> {
+    args.check_options(&["input", "jobs", "timeout"])?;
+
+    let workers = match args.option("jobs") {
+        Some(n) => n
+            .parse()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("invalid job count `{n}`"))?,
+        None => thread::available_parallelism().map_or(1, usize::from),
+    };
+
+    let timeout = args
+        .option("timeout")
+        .map(|secs| {
+            secs.parse()
+                .ok()
+                .filter(|&secs: &f64| secs > 0.0 && secs.is_finite())
+                .map(Duration::from_secs_f64)
+                .ok_or_else(|| format!("invalid timeout `{secs}`"))
+        })
+        .transpose()?;
+
+    let mut days = registry::SOLVERS.iter().map(|s| s.day).collect::<Vec<_>>();
+    days.dedup();
+
+    let mut jobs = Vec::new();
+
+    for day in days {
+        let solvers = registry::find(Some(day), None, None).collect::<Vec<_>>();
+
+        for input in selected_inputs(args, day)? {
+            jobs.push(Job {
+                day,
+                solvers: solvers.clone(),
+                input,
+            });
+        }
+    }
+
+    let labels = jobs
+        .iter()
+        .map(|job| match job.input.name.as_str() {
+            DEFAULT_INPUT => format!("day{}", job.day),
+            name => format!("day{} [{name}]", job.day),
+        })
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let results = run_isolated(jobs, workers, timeout, run_job);
+    let wall_time = start.elapsed();
+
+    let width = labels.iter().map(String::len).max().unwrap_or(0);
+    let (mut succeeded, mut failed) = (0, 0);
+
+    println!("{:width$}  {:9}  {:>12}  answers", "day", "status", "time");
+
+    for (label, (result, elapsed)) in labels.iter().zip(&results) {
+        let (status, details, success) = describe(result);
+
+        if success {
+            succeeded += 1;
+        } else {
+            failed += 1;
+        }
+
+        println!(
+            "{label:width$}  {status:9}  {:>12}  {details}",
+            format!("{elapsed:.2?}")
+        );
+    }
+
+    println!("{succeeded} succeeded, {failed} failed in {wall_time:.2?}");
+
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(n: &u64) -> u64 {
+        match n {
+            0 => panic!("no zeroes"),
+            n if *n >= 1000 => {
+                thread::sleep(Duration::from_millis(*n));
+                *n
+            }
+            n => n * 2,
+        }
+    }
+
+    #[test]
+    fn isolates_failing_jobs() {
+        let results = run_isolated(vec![1, 0, 3], 2, None, job)
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            [Ok(2), Err(Failure::Panicked("no zeroes".into())), Ok(6)]
+        );
+    }
+
+    #[test]
+    fn abandons_slow_jobs() {
+        let timeout = Duration::from_millis(100);
+        let results = run_isolated(vec![5000, 2, 5000, 4], 2, Some(timeout), job)
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            [Err(Failure::TimedOut), Ok(4), Err(Failure::TimedOut), Ok(8)]
+        );
+    }
+}
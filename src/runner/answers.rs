@@ -0,0 +1,94 @@
+use std::{collections::HashMap, fs};
+
+use toml::{Table, Value};
+
+/// File holding the accepted answers, as `[day<N>.<input>]` tables of `part<M>` strings.
+pub const ANSWERS_PATH: &str = "answers/2022.toml";
+
+/// Accepted answers, keyed by day, part and input name.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u8, u8, String), String>,
+}
+
+impl Answers {
+    /// Reads [`ANSWERS_PATH`], treating a missing file as having no answers.
+    pub fn load() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_PATH) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{ANSWERS_PATH}: {e}")),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("can't read {ANSWERS_PATH}: {e}")),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table = text.parse::<Table>().map_err(|e| e.to_string())?;
+        let mut answers = HashMap::new();
+
+        for (day_key, inputs) in &table {
+            let day = parse_key(day_key, "day")?;
+
+            for (input, parts) in as_table(inputs, day_key)? {
+                for (part_key, answer) in as_table(parts, input)? {
+                    let part = parse_key(part_key, "part")?;
+                    let answer = answer.as_str().ok_or_else(|| {
+                        format!("answer for {day_key}.{input}.{part_key} isn't a string")
+                    })?;
+
+                    answers.insert((day, part, input.clone()), answer.to_owned());
+                }
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, input.to_owned()))
+            .map(String::as_str)
+    }
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u8, String> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("expected a key like `{prefix}1`, found `{key}`"))
+}
+
+fn as_table<'a>(value: &'a Value, key: &str) -> Result<&'a Table, String> {
+    value
+        .as_table()
+        .ok_or_else(|| format!("`{key}` should be a table"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::DEFAULT_INPUT;
+
+    #[test]
+    fn parses_answers() {
+        let answers = Answers::parse(
+            r##"
+            [day1.default]
+            part1 = "24000"
+
+            [day10.small]
+            part2 = "#.\n.#"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(1, 1, DEFAULT_INPUT), Some("24000"));
+        assert_eq!(answers.get(1, 2, DEFAULT_INPUT), None);
+        assert_eq!(answers.get(10, 2, "small"), Some("#.\n.#"));
+    }
+
+    #[test]
+    fn rejects_bad_keys() {
+        assert!(Answers::parse("[one.default]\npart1 = \"1\"").is_err());
+        assert!(Answers::parse("[day1.default]\npart3x = \"1\"").is_err());
+        assert!(Answers::parse("[day1.default]\npart1 = 1").is_err());
+    }
+}
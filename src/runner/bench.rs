@@ -0,0 +1,304 @@
+use std::{collections::HashMap, fs, time::Duration};
+
+use serde_json::{json, Value};
+
+use super::{run_solver, selected_inputs, Args, DEFAULT_INPUT};
+use crate::registry::{self, Solver};
+
+const DEFAULT_ITERATIONS: u32 = 100;
+
+/// Median slowdown against the baseline above which a solver is flagged.
+const REGRESSION_THRESHOLD: f64 = 0.10;
+
+/// Timing summary over every iteration of a solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    p95: Duration,
+}
+
+impl Stats {
+    fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        samples.sort_unstable();
+
+        Some(Self {
+            min: *samples.first()?,
+            median: percentile(samples, 0.5),
+            p95: percentile(samples, 0.95),
+        })
+    }
+
+    /// Input bytes processed per second at the median time.
+    fn throughput(&self, bytes: usize) -> f64 {
+        bytes as f64 / self.median.as_secs_f64()
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty `samples`.
+fn percentile(samples: &[Duration], p: f64) -> Duration {
+    let rank = (p * samples.len() as f64).ceil() as usize;
+    samples[rank.clamp(1, samples.len()) - 1]
+}
+
+/// Day, part, variant and input name.
+type BenchKey = (u8, u8, String, String);
+
+fn key(solver: &Solver, input: &str) -> BenchKey {
+    (
+        solver.day,
+        solver.part,
+        solver.variant.to_owned(),
+        input.to_owned(),
+    )
+}
+
+/// The timings of a solver on one input, where `stats` include parsing.
+struct BenchResult {
+    solver: &'static Solver,
+    input: String,
+    input_bytes: usize,
+    stats: Stats,
+    parse_median: Duration,
+}
+
+fn to_json(results: &[BenchResult]) -> Value {
+    let results = results
+        .iter()
+        .map(|result| {
+            let BenchResult {
+                solver,
+                input,
+                input_bytes,
+                stats,
+                parse_median,
+            } = result;
+
+            json!({
+                "day": solver.day,
+                "part": solver.part,
+                "variant": solver.variant,
+                "input": input,
+                "input_bytes": input_bytes,
+                "min_ns": stats.min.as_nanos() as u64,
+                "median_ns": stats.median.as_nanos() as u64,
+                "p95_ns": stats.p95.as_nanos() as u64,
+                "parse_median_ns": parse_median.as_nanos() as u64,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "results": results })
+}
+
+fn from_json(json: &Value) -> Result<HashMap<BenchKey, Stats>, String> {
+    let results = json["results"]
+        .as_array()
+        .ok_or("baseline has no `results` list")?;
+
+    results
+        .iter()
+        .map(|result| {
+            let int = |field: &str| {
+                result[field]
+                    .as_u64()
+                    .ok_or_else(|| format!("baseline entry is missing `{field}`"))
+            };
+            let nanos = |field: &str| int(field).map(Duration::from_nanos);
+
+            let key = (
+                int("day")? as u8,
+                int("part")? as u8,
+                result["variant"]
+                    .as_str()
+                    .ok_or("baseline entry is missing `variant`")?
+                    .to_owned(),
+                result["input"].as_str().unwrap_or(DEFAULT_INPUT).to_owned(),
+            );
+            let stats = Stats {
+                min: nanos("min_ns")?,
+                median: nanos("median_ns")?,
+                p95: nanos("p95_ns")?,
+            };
+
+            Ok((key, stats))
+        })
+        .collect()
+}
+
+fn load_baseline(path: &str) -> Result<HashMap<BenchKey, Stats>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("can't read {path}: {e}"))?;
+    let json = serde_json::from_str(&text).map_err(|e| format!("{path}: {e}"))?;
+
+    from_json(&json).map_err(|e| format!("{path}: {e}"))
+}
+
+/// Relative change of the median, e.g. `0.25` for 25% slower.
+fn median_change(baseline: &Stats, current: &Stats) -> f64 {
+    current.median.as_secs_f64() / baseline.median.as_secs_f64() - 1.0
+}
+
+fn format_throughput(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1e9 {
+        format!("{:.2} GB/s", bytes_per_sec / 1e9)
+    } else {
+        format!("{:.1} MB/s", bytes_per_sec / 1e6)
+    }
+}
+
+/// Times every matching solver, optionally saving the results or comparing against a baseline.
+pub(super) fn bench(args: &Args) -> Result<bool, String> {
+    args.check_options(&["variant", "input", "iterations", "save", "baseline"])?;
+
+    let iterations = match args.option("iterations") {
+        Some(n) => n
+            .parse()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("invalid iteration count `{n}`"))?,
+        None => DEFAULT_ITERATIONS,
+    };
+
+    let baseline = args.option("baseline").map(load_baseline).transpose()?;
+
+    let solvers = registry::find(args.day(1)?, args.part(2)?, args.option("variant"));
+    let mut results = Vec::new();
+    let mut success = true;
+
+    for solver in solvers {
+        let inputs = selected_inputs(args, solver.day)?;
+
+        for input in inputs {
+            let text = input
+                .load()
+                .map_err(|e| format!("can't read {}: {e}", input.path.display()))?;
+
+            let label = format!(
+                "day{} part{} {} [{}]",
+                solver.day, solver.part, solver.variant, input.name
+            );
+
+            // One untimed run to warm up caches and surface errors early.
+            if let Err(error) = run_solver(solver, &text).answer {
+                println!("{label}: error: {error}");
+                success = false;
+                continue;
+            }
+
+            let (mut samples, mut parse_samples): (Vec<_>, Vec<_>) = (0..iterations)
+                .map(|_| {
+                    let outcome = run_solver(solver, &text);
+                    (
+                        outcome.parse_elapsed + outcome.solve_elapsed,
+                        outcome.parse_elapsed,
+                    )
+                })
+                .unzip();
+
+            let stats = Stats::from_samples(&mut samples).expect("at least one iteration");
+            let parse_median = Stats::from_samples(&mut parse_samples)
+                .expect("at least one iteration")
+                .median;
+
+            print!(
+                "{label}: min {:?}, median {:?} (parse {:?}), p95 {:?}, {}",
+                stats.min,
+                stats.median,
+                parse_median,
+                stats.p95,
+                format_throughput(stats.throughput(text.len()))
+            );
+
+            match baseline
+                .as_ref()
+                .and_then(|b| b.get(&key(solver, &input.name)))
+            {
+                Some(base) => {
+                    let change = median_change(base, &stats);
+                    let flag = if change > REGRESSION_THRESHOLD {
+                        " REGRESSED"
+                    } else {
+                        ""
+                    };
+
+                    println!(" ({:+.1}% vs baseline{flag})", change * 100.0);
+                }
+                None if baseline.is_some() => println!(" (not in baseline)"),
+                None => println!(),
+            }
+
+            results.push(BenchResult {
+                solver,
+                input: input.name,
+                input_bytes: text.len(),
+                stats,
+                parse_median,
+            });
+        }
+    }
+
+    if let Some(path) = args.option("save") {
+        let json = serde_json::to_string_pretty(&to_json(&results)).expect("valid JSON");
+        fs::write(path, json + "\n").map_err(|e| format!("can't write {path}: {e}"))?;
+        println!("saved results to {path}");
+    }
+
+    Ok(success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn summarises_samples() {
+        let mut samples = (1..=20).rev().map(ms).collect::<Vec<_>>();
+
+        assert_eq!(
+            Stats::from_samples(&mut samples),
+            Some(Stats {
+                min: ms(1),
+                median: ms(10),
+                p95: ms(19),
+            })
+        );
+        assert_eq!(Stats::from_samples(&mut []), None);
+    }
+
+    #[test]
+    fn round_trips_baselines() {
+        let solver = registry::find(Some(1), Some(1), None).next().unwrap();
+        let stats = Stats {
+            min: ms(1),
+            median: ms(2),
+            p95: ms(4),
+        };
+
+        let result = BenchResult {
+            solver,
+            input: "alice".into(),
+            input_bytes: 100,
+            stats,
+            parse_median: ms(1),
+        };
+
+        let baseline = from_json(&to_json(&[result])).unwrap();
+        let base = &baseline[&key(solver, "alice")];
+
+        assert_eq!(base, &stats);
+        assert_eq!(
+            median_change(
+                base,
+                &Stats {
+                    median: ms(3),
+                    ..stats
+                }
+            ),
+            0.5
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{Args, GENERATED_DIR};
+use crate::gen;
+
+const DEFAULT_SIZE: usize = 1000;
+const DEFAULT_NAME: &str = "generated";
+
+/// Writes an input of `size` for each of `days` to `dir` as `day<N>/<name>.txt`, returning the
+/// paths written.
+fn write_inputs(
+    dir: &Path,
+    days: &[u8],
+    size: usize,
+    seed: u64,
+    name: &str,
+) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for &day in days {
+        let Some(input) = gen::generate(day, size, seed) else {
+            continue;
+        };
+
+        let day_dir = dir.join(format!("day{day}"));
+        fs::create_dir_all(&day_dir)?;
+
+        let path = day_dir.join(format!("{name}.txt"));
+        fs::write(&path, input + "\n")?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Generates random inputs, which the other commands use when given `--input generated/<name>`.
+pub(super) fn generate(args: &Args) -> Result<bool, String> {
+    args.check_options(&["size", "seed", "name"])?;
+
+    let days = match args.day(1)? {
+        Some(day) if gen::find(day).is_none() => return Err(format!("no generator for day {day}")),
+        Some(day) => vec![day],
+        None => gen::GENERATORS.iter().map(|&(day, _)| day).collect(),
+    };
+
+    let size = match args.option("size") {
+        Some(n) => n.parse().map_err(|_| format!("invalid size `{n}`"))?,
+        None => DEFAULT_SIZE,
+    };
+
+    let seed = match args.option("seed") {
+        Some(n) => n.parse().map_err(|_| format!("invalid seed `{n}`"))?,
+        None => 0,
+    };
+
+    let name = args.option("name").unwrap_or(DEFAULT_NAME);
+
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return Err(format!("invalid input name `{name}`"));
+    }
+
+    let paths = write_inputs(Path::new(GENERATED_DIR), &days, size, seed, name)
+        .map_err(|e| format!("can't write inputs: {e}"))?;
+
+    for path in paths {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::inputs_in;
+
+    #[test]
+    fn writes_named_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc_2022_gen_{}", std::process::id()));
+
+        let paths = write_inputs(&dir, &[2, 25], 10, 7, "stress").unwrap();
+        let inputs = inputs_in(&dir, 2).unwrap();
+        let text = inputs[0].load().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(paths, [dir.join("day2/stress.txt")]);
+        assert_eq!(inputs[0].name, "stress");
+        assert_eq!(text.lines().count(), 10);
+        assert_eq!(Some(text), gen::generate(2, 10, 7));
+    }
+}
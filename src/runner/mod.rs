@@ -0,0 +1,577 @@
+//! The `aoc_2022` command line, dispatching to the solvers in [`crate::registry`].
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    input,
+    memory::{self, MemoryUsage},
+    registry::{self, Solver, SolverError},
+};
+
+mod all;
+pub mod answers;
+mod bench;
+mod generate;
+mod scaffold;
+mod stats;
+mod stream;
+mod verify;
+
+const USAGE: &str = "Usage: aoc_2022 <command> [options]
+
+Commands:
+  run <day> [part] [--variant <name>] [--input <name>] [--format text|json]
+                                              Solve a day with each of its puzzle inputs
+  all [--input <name>] [--jobs <n>] [--timeout <seconds>]
+                                              Solve every day on a pool of threads, isolating
+                                              panics and giving up on days that overrun
+  bench [day] [part] [--variant <name>] [--input <name>] [--iterations <n>]
+        [--save <file>] [--baseline <file>]
+                                              Time solvers over repeated runs, reporting
+                                              min/median/p95 and throughput, optionally
+                                              saving or comparing against a JSON baseline
+  verify [day] [part] [--input <name>]        Check solvers against answers/2022.toml
+  stats <day> [--input <name>] [--format text|json]
+                                              Describe a day's inputs, so far the spread of
+                                              calories in day 1 and every reading of day 2's
+                                              strategy guide
+  stream <day> [file] [--top <n>] [--format text|json]
+                                              Solve a day a line at a time from a file, or
+                                              stdin if none is given, listing the best elves;
+                                              so far only day 1
+  list                                        Show every registered solver
+  gen [day] [--size <n>] [--seed <n>] [--name <name>]
+                                              Write random inputs of the given size for
+                                              stress testing, as input/generated/day<N>/<name>.txt
+  new <day> [--puzzle <file>]                 Create a day from day_template.rs, taking the
+                                              example and its answers from a saved puzzle page
+
+Each day's default input is input/2022/day<N>.txt, and further inputs can be added as
+input/2022/day<N>/<name>.txt. Commands use every input unless one is picked with --input.
+Generated inputs are only used when picked with --input generated/<name>.
+
+Built with the count-allocations feature, run also reports the peak heap use and allocation
+count of each generator and solver.";
+
+/// Directory holding the puzzle inputs, as `day<N>.txt` and `day<N>/<name>.txt`.
+pub const INPUT_DIR: &str = "input/2022";
+
+/// Directory the `gen` command writes random inputs to, as `day<N>/<name>.txt`.
+///
+/// These are kept apart from the puzzle inputs, since they can be huge, and only used when picked
+/// with `--input generated/<name>`.
+pub const GENERATED_DIR: &str = "input/generated";
+
+const GENERATED_PREFIX: &str = "generated/";
+
+/// Name of the input stored directly in [`INPUT_DIR`] as `day<N>.txt`.
+pub const DEFAULT_INPUT: &str = "default";
+
+/// Positional arguments plus `--flag value` options.
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("missing value for --{name}"))?;
+                options.insert(name.to_owned(), value);
+            } else {
+                positional.push(arg);
+            }
+        }
+
+        Ok(Self {
+            positional,
+            options,
+        })
+    }
+
+    fn positional(&self, index: usize) -> Option<&str> {
+        self.positional.get(index).map(String::as_str)
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn day(&self, index: usize) -> Result<Option<u8>, String> {
+        self.positional(index)
+            .map(|day| {
+                day.trim_start_matches("day")
+                    .parse()
+                    .map_err(|_| format!("invalid day `{day}`"))
+            })
+            .transpose()
+    }
+
+    fn format(&self) -> Result<Format, String> {
+        match self.option("format") {
+            None | Some("text") => Ok(Format::Text),
+            Some("json") => Ok(Format::Json),
+            Some(format) => Err(format!("unknown format `{format}`")),
+        }
+    }
+
+    fn part(&self, index: usize) -> Result<Option<u8>, String> {
+        self.positional(index)
+            .map(|part| match part.trim_start_matches("part") {
+                "1" => Ok(1),
+                "2" => Ok(2),
+                _ => Err(format!("invalid part `{part}`")),
+            })
+            .transpose()
+    }
+
+    /// Fails if any options other than `allowed` were given.
+    fn check_options(&self, allowed: &[&str]) -> Result<(), String> {
+        match self.options.keys().find(|o| !allowed.contains(&o.as_str())) {
+            Some(option) => Err(format!("unknown option --{option}")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// The outcome of running a single solver once.
+pub struct Outcome {
+    pub solver: &'static Solver,
+    pub answer: Result<String, SolverError>,
+    /// Time spent in the day's generator, shared by every solver run on the same model.
+    pub parse_elapsed: Duration,
+    pub solve_elapsed: Duration,
+    /// Heap used by the generator and the solver, when [allocations are counted](memory::ENABLED).
+    pub parse_memory: Option<MemoryUsage>,
+    pub solve_memory: Option<MemoryUsage>,
+}
+
+/// Parses `input` once and runs each of `solvers` on the model, which all have to be for one day.
+pub fn run_solvers(solvers: &[&'static Solver], input: &str) -> Vec<Outcome> {
+    let Some(first) = solvers.first() else {
+        return Vec::new();
+    };
+
+    assert!(
+        solvers.iter().all(|s| s.day == first.day),
+        "solvers for different days can't share a model"
+    );
+
+    let start = Instant::now();
+    let (model, parse_memory) = memory::measure(|| (first.parse)(input));
+    let parse_elapsed = start.elapsed();
+
+    solvers
+        .iter()
+        .map(|&solver| {
+            let start = Instant::now();
+            let (answer, solve_memory) = memory::measure(|| match &model {
+                Ok(model) => (solver.solve)(&**model),
+                Err(error) => Err(error.to_string().into()),
+            });
+
+            Outcome {
+                solver,
+                answer,
+                parse_elapsed,
+                solve_elapsed: start.elapsed(),
+                parse_memory,
+                solve_memory,
+            }
+        })
+        .collect()
+}
+
+pub fn run_solver(solver: &'static Solver, input: &str) -> Outcome {
+    run_solvers(&[solver], input)
+        .pop()
+        .expect("one outcome per solver")
+}
+
+/// Path of the default input for `day`.
+pub fn input_path(day: u8) -> PathBuf {
+    PathBuf::from(INPUT_DIR).join(format!("day{day}.txt"))
+}
+
+/// A puzzle input for some day, named after its file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedInput {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl NamedInput {
+    /// Reads the input, [normalized](crate::input::normalize) for the solvers.
+    pub fn load(&self) -> io::Result<String> {
+        let input = fs::read_to_string(&self.path)?;
+        Ok(input::normalize(&input).into_owned())
+    }
+}
+
+/// Every input for `day` under `dir`, the default one first and the rest sorted by name.
+fn inputs_in(dir: &Path, day: u8) -> io::Result<Vec<NamedInput>> {
+    let mut inputs = Vec::new();
+
+    let default = dir.join(format!("day{day}.txt"));
+    if default.is_file() {
+        inputs.push(NamedInput {
+            name: DEFAULT_INPUT.to_owned(),
+            path: default,
+        });
+    }
+
+    let named_dir = dir.join(format!("day{day}"));
+    if named_dir.is_dir() {
+        let mut named = Vec::new();
+
+        for entry in fs::read_dir(named_dir)? {
+            let path = entry?.path();
+
+            if path.extension().is_some_and(|e| e == "txt") {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    named.push(NamedInput {
+                        name: name.to_owned(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+
+        named.sort_by(|a, b| a.name.cmp(&b.name));
+        inputs.extend(named);
+    }
+
+    Ok(inputs)
+}
+
+/// Every input for `day`, see [`INPUT_DIR`].
+pub fn find_inputs(day: u8) -> io::Result<Vec<NamedInput>> {
+    inputs_in(Path::new(INPUT_DIR), day)
+}
+
+/// Reads the default input for `day`.
+pub fn load_input(day: u8) -> io::Result<String> {
+    NamedInput {
+        name: DEFAULT_INPUT.to_owned(),
+        path: input_path(day),
+    }
+    .load()
+}
+
+/// The generated input for `day` called `name` under `dir`, named `generated/<name>`.
+fn generated_input(dir: &Path, day: u8, name: &str) -> Option<NamedInput> {
+    let path = dir.join(format!("day{day}")).join(format!("{name}.txt"));
+
+    path.is_file().then(|| NamedInput {
+        name: format!("{GENERATED_PREFIX}{name}"),
+        path,
+    })
+}
+
+/// The inputs for `day` picked by `--input`, or all of the puzzle inputs.
+fn selected_inputs(args: &Args, day: u8) -> Result<Vec<NamedInput>, String> {
+    if let Some(name) = args
+        .option("input")
+        .and_then(|name| name.strip_prefix(GENERATED_PREFIX))
+    {
+        return Ok(generated_input(Path::new(GENERATED_DIR), day, name)
+            .into_iter()
+            .collect());
+    }
+
+    let mut inputs = find_inputs(day).map_err(|e| format!("can't list day {day} inputs: {e}"))?;
+
+    if let Some(name) = args.option("input") {
+        inputs.retain(|input| input.name == name);
+    }
+
+    Ok(inputs)
+}
+
+/// How long something took, followed by its heap use if allocations are counted.
+fn cost(elapsed: Duration, memory: Option<MemoryUsage>) -> String {
+    match memory {
+        Some(usage) => format!(
+            "{elapsed:?}, peak {}, {} allocation{}",
+            memory::format_bytes(usage.peak_bytes as u64),
+            usage.allocations,
+            if usage.allocations == 1 { "" } else { "s" }
+        ),
+        None => format!("{elapsed:?}"),
+    }
+}
+
+fn print_outcome(outcome: &Outcome, input: &str) {
+    let Solver {
+        day, part, variant, ..
+    } = outcome.solver;
+
+    let mut label = format!("Day {day} part {part}");
+
+    if *variant != registry::DEFAULT_VARIANT {
+        label += &format!(" ({variant})");
+    }
+
+    if input != DEFAULT_INPUT {
+        label += &format!(" [{input}]");
+    }
+
+    let cost = cost(outcome.solve_elapsed, outcome.solve_memory);
+
+    match &outcome.answer {
+        Ok(answer) if answer.contains('\n') => println!("{label}: ({cost})\n{answer}"),
+        Ok(answer) => println!("{label}: {answer} ({cost})"),
+        Err(error) => println!("{label}: error: {error}"),
+    }
+}
+
+fn memory_json(memory: Option<MemoryUsage>) -> Value {
+    match memory {
+        Some(usage) => json!({
+            "peak_bytes": usage.peak_bytes,
+            "allocations": usage.allocations,
+            "allocated_bytes": usage.allocated_bytes,
+        }),
+        None => Value::Null,
+    }
+}
+
+fn outcome_json(outcome: &Outcome, input: &str) -> Value {
+    let (answer, error) = match &outcome.answer {
+        Ok(answer) => (Some(answer.clone()), None),
+        Err(error) => (None, Some(error.to_string())),
+    };
+
+    json!({
+        "day": outcome.solver.day,
+        "part": outcome.solver.part,
+        "variant": outcome.solver.variant,
+        "input": input,
+        "answer": answer,
+        "parse_ns": outcome.parse_elapsed.as_nanos() as u64,
+        "solve_ns": outcome.solve_elapsed.as_nanos() as u64,
+        "parse_memory": memory_json(outcome.parse_memory),
+        "solve_memory": memory_json(outcome.solve_memory),
+        "error": error,
+    })
+}
+
+fn run(args: &Args) -> Result<bool, String> {
+    args.check_options(&["variant", "input", "format"])?;
+
+    let format = args.format()?;
+
+    let day = args.day(1)?.ok_or("missing day")?;
+    let part = args.part(2)?;
+    let variant = args.option("variant");
+
+    let solvers = registry::find(Some(day), part, variant).collect::<Vec<_>>();
+
+    if solvers.is_empty() {
+        return Err(format!("no solvers registered for day {day}"));
+    }
+
+    let inputs = selected_inputs(args, day)?;
+
+    if inputs.is_empty() {
+        return Err(format!("no matching inputs for day {day}"));
+    }
+
+    let mut success = true;
+    let mut results = Vec::new();
+
+    for input in inputs {
+        let text = input
+            .load()
+            .map_err(|e| format!("can't read {}: {e}", input.path.display()))?;
+
+        let outcomes = run_solvers(&solvers, &text);
+
+        if let (Format::Text, Some(outcome)) = (format, outcomes.first()) {
+            let cost = cost(outcome.parse_elapsed, outcome.parse_memory);
+
+            match input.name.as_str() {
+                DEFAULT_INPUT => println!("Day {day} parse: ({cost})"),
+                name => println!("Day {day} [{name}] parse: ({cost})"),
+            }
+        }
+
+        for outcome in outcomes {
+            success &= outcome.answer.is_ok();
+
+            match format {
+                Format::Text => print_outcome(&outcome, &input.name),
+                Format::Json => results.push(outcome_json(&outcome, &input.name)),
+            }
+        }
+    }
+
+    if format == Format::Json {
+        let report = json!({ "results": results });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("valid JSON")
+        );
+    }
+
+    Ok(success)
+}
+
+fn list() {
+    for Solver {
+        day,
+        part,
+        variant,
+        name,
+        ..
+    } in registry::SOLVERS
+    {
+        match name {
+            Some(name) => println!("day{day} part{part} {variant}: {name}"),
+            None => println!("day{day} part{part} {variant}"),
+        }
+    }
+}
+
+/// Runs the command line given by `args`, not including the program name.
+pub fn main(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let result = Args::parse(args).and_then(|args| match args.positional(0) {
+        Some("run") => run(&args),
+        Some("all") => all::all(&args),
+        Some("bench") => bench::bench(&args),
+        Some("verify") => verify::verify(&args),
+        Some("stats") => stats::stats(&args),
+        Some("stream") => stream::stream(&args),
+        Some("new") => scaffold::scaffold(&args),
+        Some("gen") => generate::generate(&args),
+        Some("list") => {
+            list();
+            Ok(true)
+        }
+        Some(command) => Err(format!("unknown command `{command}`")),
+        None => Err("missing command".to_owned()),
+    });
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> Args {
+        Args::parse(args.split_whitespace().map(str::to_owned)).unwrap()
+    }
+
+    #[test]
+    fn parses_arguments() {
+        let args = args("run day6 2 --variant naive");
+
+        assert_eq!(args.day(1), Ok(Some(6)));
+        assert_eq!(args.part(2), Ok(Some(2)));
+        assert_eq!(args.option("variant"), Some("naive"));
+        assert_eq!(
+            args.check_options(&[]),
+            Err("unknown option --variant".into())
+        );
+        assert_eq!(args.format(), Ok(Format::Text));
+    }
+
+    #[test]
+    fn reports_outcomes_as_json() {
+        let solver = registry::find(Some(1), Some(1), None).next().unwrap();
+
+        let json = outcome_json(&run_solver(solver, "1\n2\n\n4"), "alice");
+        assert_eq!(json["input"], "alice");
+        assert_eq!(json["answer"], "4");
+        assert!(json["error"].is_null());
+        assert!(json["parse_ns"].is_u64() && json["solve_ns"].is_u64());
+        assert_eq!(
+            json["solve_memory"]["allocations"].is_u64(),
+            memory::ENABLED
+        );
+
+        let json = outcome_json(&run_solver(solver, "x"), DEFAULT_INPUT);
+        assert!(json["answer"].is_null());
+        assert_eq!(
+            json["error"],
+            "day 1 input, line 1, column 1: expected digit, found 'x'"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(args("run x").day(1).is_err());
+        assert!(args("run 1 3").part(2).is_err());
+        assert!(Args::parse(["--variant".to_owned()]).is_err());
+        assert!(args("run 1 --format xml").format().is_err());
+    }
+
+    #[test]
+    fn finds_named_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc_2022_inputs_{}", std::process::id()));
+        fs::create_dir_all(dir.join("day3")).unwrap();
+
+        for file in [
+            "day3.txt",
+            "day3/bob.txt",
+            "day3/alice.txt",
+            "day3/notes.md",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let inputs = inputs_in(&dir, 3).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names = inputs.iter().map(|i| i.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, [DEFAULT_INPUT, "alice", "bob"]);
+        assert_eq!(inputs[1].path, dir.join("day3/alice.txt"));
+        assert!(inputs_in(&dir, 4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn finds_generated_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc_2022_generated_{}", std::process::id()));
+        fs::create_dir_all(dir.join("day3")).unwrap();
+        fs::write(dir.join("day3/big.txt"), "").unwrap();
+
+        let found = generated_input(&dir, 3, "big");
+        let missing = generated_input(&dir, 3, "small");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let found = found.unwrap();
+        assert_eq!(found.name, "generated/big");
+        assert_eq!(found.path, dir.join("day3/big.txt"));
+        assert_eq!(missing, None);
+    }
+}
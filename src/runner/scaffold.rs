@@ -0,0 +1,350 @@
+use std::{fs, path::Path};
+
+use super::{input_path, Args};
+
+const TEMPLATE_PATH: &str = "day_template.rs";
+const LIB_PATH: &str = "src/lib.rs";
+const REGISTRY_PATH: &str = "src/registry.rs";
+
+/// The generator every day made from the template starts with.
+const GENERATOR: &str = "parse_input";
+
+/// The title and first example of a puzzle description, with the answers given for it.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Example {
+    title: Option<String>,
+    input: String,
+    answers: Vec<String>,
+}
+
+/// Pulls the example out of a saved puzzle page.
+///
+/// The title comes from the `--- Day N: Title ---` heading, the example input is the first
+/// `<pre><code>` block, and each part's answer is the last emphasised `<code>` in that part's
+/// description, which is how the puzzles phrase them.
+fn parse_puzzle(html: &str) -> Result<Example, String> {
+    let input = between(html, "<pre><code>", "</code></pre>")
+        .map(|code| unescape(&strip_tags(code)))
+        .ok_or("puzzle has no example input")?;
+
+    let title = between(html, "<h2>--- ", " ---</h2>")
+        .and_then(|heading| heading.split_once(": "))
+        .map(|(_, title)| unescape(title));
+
+    let answers = html
+        .split(r#"<article class="day-desc">"#)
+        .skip(1)
+        .filter_map(|part| {
+            let part = &part[..part.find("</article>").unwrap_or(part.len())];
+            let start = part.rfind("<code><em>")?;
+            between(&part[start..], "<code><em>", "</em></code>").map(unescape)
+        })
+        .collect();
+
+    Ok(Example {
+        title,
+        input: input.trim_end_matches('\n').to_owned(),
+        answers,
+    })
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let text = &text[text.find(start)? + start.len()..];
+    Some(&text[..text.find(end)?])
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn unescape(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Numbers are asserted as they are, anything else as a string.
+fn answer_literal(answer: &str) -> String {
+    if !answer.is_empty() && answer.bytes().all(|b| b.is_ascii_digit()) {
+        answer.to_owned()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+fn replace_once(text: &str, from: &str, to: &str) -> Result<String, String> {
+    if !text.contains(from) {
+        return Err(format!("{TEMPLATE_PATH} has no `{from}`"));
+    }
+
+    Ok(text.replacen(from, to, 1))
+}
+
+/// Fills in the template for `day`, enabling the part 2 test when its answer is known.
+fn render_day(template: &str, day: u8, example: Option<&Example>) -> Result<String, String> {
+    let mut code = replace_once(
+        template,
+        "const DAY: u8 = 0;",
+        &format!("const DAY: u8 = {day};"),
+    )?
+    .replace("pub struct Day;", &format!("pub struct Day{day:02};"))
+    .replace(
+        "impl Solution for Day {",
+        &format!("impl Solution for Day{day:02} {{"),
+    )
+    .replace(
+        "#[aoc_generator(day)]",
+        &format!("#[aoc_generator(day{day})]"),
+    )
+    .replace("#[aoc(day, ", &format!("#[aoc(day{day}, "));
+
+    let Some(example) = example else {
+        return Ok(code);
+    };
+
+    if let Some(title) = &example.title {
+        code = replace_once(
+            &code,
+            "    const DAY: u8 = DAY;\n",
+            &format!(
+                "    const DAY: u8 = DAY;\n    \
+                 const NAME: Option<&'static str> = Some({title:?});\n"
+            ),
+        )?;
+    }
+
+    let input = example.input.replace('\\', "\\\\").replace('"', "\\\"");
+    code = replace_once(
+        &code,
+        "static TEST_INPUT: &str = \"\";",
+        &format!("static TEST_INPUT: &str = \"{input}\";"),
+    )?;
+
+    for (part, answer) in (1..).zip(&example.answers) {
+        let check = format!("assert_eq!(run_part{part}(&{GENERATOR}(TEST_INPUT).unwrap()), ");
+        code = replace_once(
+            &code,
+            &format!("{check}0);"),
+            &format!("{check}{});", answer_literal(answer)),
+        )?;
+    }
+
+    if example.answers.len() > 1 {
+        code = replace_once(&code, "/* #[test]", "#[test]")?;
+        code = code.replace("} */", "}");
+    }
+
+    Ok(code)
+}
+
+/// Adds `pub mod dayNN;` to `lib.rs`, keeping the days in order.
+fn add_module(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("pub mod day{day:02};");
+
+    if lib.lines().any(|line| line == module) {
+        return Err(format!("{LIB_PATH} already declares day{day:02}"));
+    }
+
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod day"))
+        .map(|(i, line)| (i, *line))
+        .collect::<Vec<_>>();
+
+    let index = match days.iter().find(|(_, line)| **line > *module) {
+        Some(&(i, _)) => i,
+        None => days
+            .last()
+            .map(|&(i, _)| i + 1)
+            .ok_or_else(|| format!("{LIB_PATH} has no day modules"))?,
+    };
+
+    lines.insert(index, &module);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Registers the solution of `day` in the `solvers!` list of `registry.rs`.
+fn add_solution(registry: &str, day: u8) -> Result<String, String> {
+    const START: &str = "solvers! {\n";
+
+    let start = registry
+        .find(START)
+        .map(|i| i + START.len())
+        .ok_or_else(|| format!("{REGISTRY_PATH} has no `solvers!` list"))?;
+    let end = start
+        + registry[start..]
+            .find("\n}")
+            .ok_or_else(|| format!("{REGISTRY_PATH} has an unterminated `solvers!` list"))?
+        + 1;
+
+    let entry_day = |line: &str| {
+        let day = line.trim().strip_prefix("day")?.split("::").next()?;
+        day.parse::<u8>().ok()
+    };
+
+    if registry[start..end]
+        .lines()
+        .any(|l| entry_day(l) == Some(day))
+    {
+        return Err(format!("{REGISTRY_PATH} already has solvers for day {day}"));
+    }
+
+    let insert_at = start
+        + registry[start..end]
+            .lines()
+            .take_while(|l| entry_day(l).is_none_or(|d| d < day))
+            .map(|l| l.len() + 1)
+            .sum::<usize>();
+
+    let entry = format!("    day{day:02}::Day{day:02};\n");
+
+    Ok([&registry[..insert_at], &entry, &registry[insert_at..]].concat())
+}
+
+/// Creates the module, input file and registry entries for a new day.
+pub(super) fn scaffold(args: &Args) -> Result<bool, String> {
+    args.check_options(&["puzzle"])?;
+
+    let day = args
+        .day(1)?
+        .filter(|day| (1..=25).contains(day))
+        .ok_or("expected a day from 1 to 25")?;
+
+    let module_path = format!("src/day{day:02}.rs");
+
+    if Path::new(&module_path).exists() {
+        return Err(format!("{module_path} already exists"));
+    }
+
+    let example = args
+        .option("puzzle")
+        .map(|path| {
+            fs::read_to_string(path)
+                .map_err(|e| format!("can't read {path}: {e}"))
+                .and_then(|html| parse_puzzle(&html).map_err(|e| format!("{path}: {e}")))
+        })
+        .transpose()?;
+
+    let read = |path: &str| fs::read_to_string(path).map_err(|e| format!("can't read {path}: {e}"));
+    let write = |path: &str, text: &str| {
+        fs::write(path, text).map_err(|e| format!("can't write {path}: {e}"))
+    };
+
+    let code = render_day(&read(TEMPLATE_PATH)?, day, example.as_ref())?;
+    let lib = add_module(&read(LIB_PATH)?, day)?;
+    let registry = add_solution(&read(REGISTRY_PATH)?, day)?;
+
+    write(&module_path, &code)?;
+    write(LIB_PATH, &lib)?;
+    write(REGISTRY_PATH, &registry)?;
+    println!("created {module_path}, registered day{day:02}::Day{day:02}");
+
+    let input = input_path(day);
+
+    if !input.exists() {
+        fs::write(&input, "").map_err(|e| format!("can't write {}: {e}", input.display()))?;
+        println!("created {}", input.display());
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PUZZLE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Supply Stacks ---</h2>
+<p>For example:</p>
+<pre><code>    [D]
+[N] [C]
+ 1   2
+<em>move</em> 1 from 2 to 1 &amp; "done"
+</code></pre>
+<p>Crates <code>C</code> and <code><em>N</em></code>, so the answer is <code><em>CN</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now it's <code><em>12</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn reads_puzzle_examples() {
+        assert_eq!(
+            parse_puzzle(PUZZLE),
+            Ok(Example {
+                title: Some("Supply Stacks".into()),
+                input: "    [D]\n[N] [C]\n 1   2\nmove 1 from 2 to 1 & \"done\"".into(),
+                answers: vec!["CN".into(), "12".into()],
+            })
+        );
+        assert!(parse_puzzle("<p>no example</p>").is_err());
+    }
+
+    #[test]
+    fn renders_template() {
+        let template = fs::read_to_string(TEMPLATE_PATH).unwrap();
+
+        let bare = render_day(&template, 16, None).unwrap();
+        assert!(bare.contains("const DAY: u8 = 16;"));
+        assert!(bare.contains("impl Solution for Day16 {"));
+        assert!(bare.contains("#[aoc_generator(day16)]"));
+        assert!(bare.contains("#[aoc(day16, part2)]"));
+        assert!(bare.contains("/* #[test]"));
+        assert!(!bare.contains("const NAME"));
+
+        let example = parse_puzzle(PUZZLE).unwrap();
+        let filled = render_day(&template, 16, Some(&example)).unwrap();
+        assert!(filled.contains("const NAME: Option<&'static str> = Some(\"Supply Stacks\");"));
+        assert!(filled.contains("static TEST_INPUT: &str = \"    [D]\n"));
+        assert!(filled.contains("& \\\"done\\\"\";"));
+        assert!(
+            filled.contains("assert_eq!(run_part1(&parse_input(TEST_INPUT).unwrap()), \"CN\");")
+        );
+        assert!(filled.contains("assert_eq!(run_part2(&parse_input(TEST_INPUT).unwrap()), 12);"));
+        assert!(!filled.contains("/*"));
+    }
+
+    #[test]
+    fn registers_day() {
+        let lib = "pub mod grid;\n\npub mod day01;\npub mod day03;\n\naoc_lib! { year = 2022 }\n";
+
+        assert_eq!(
+            add_module(lib, 2).unwrap(),
+            "pub mod grid;\n\npub mod day01;\npub mod day02;\npub mod day03;\n\n\
+             aoc_lib! { year = 2022 }\n"
+        );
+        assert!(add_module(lib, 4)
+            .unwrap()
+            .contains("pub mod day03;\npub mod day04;\n"));
+        assert!(add_module(lib, 3).is_err());
+
+        let registry = "solvers! {\n    day01::Day01;\n    day10::Day10 as fast;\n}\n";
+
+        assert_eq!(
+            add_solution(registry, 2).unwrap(),
+            "solvers! {\n    day01::Day01;\n    day02::Day02;\n    day10::Day10 as fast;\n}\n"
+        );
+        assert!(add_solution(registry, 11)
+            .unwrap()
+            .ends_with("    day11::Day11;\n}\n"));
+        assert!(add_solution(registry, 10).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use serde_json::json;
+
+use super::{selected_inputs, Args, Format, DEFAULT_INPUT};
+use crate::{day01, day02};
+
+/// Prints statistics about a day's inputs, which only days 1 and 2 have so far.
+pub(super) fn stats(args: &Args) -> Result<bool, String> {
+    args.check_options(&["input", "format"])?;
+
+    let format = args.format()?;
+    let day = args.day(1)?.ok_or("missing day")?;
+
+    if !matches!(day, 1 | 2) {
+        return Err(format!("no statistics for day {day}"));
+    }
+
+    let inputs = selected_inputs(args, day)?;
+
+    if inputs.is_empty() {
+        return Err(format!("no matching inputs for day {day}"));
+    }
+
+    let mut results = Vec::new();
+
+    for input in inputs {
+        let text = input
+            .load()
+            .map_err(|e| format!("can't read {}: {e}", input.path.display()))?;
+        let (report, json) = match day {
+            1 => {
+                let stats = day01::calorie_stats(&text).map_err(|e| e.to_string())?;
+                (stats.to_string(), stats.to_json())
+            }
+            _ => {
+                let game = day02::CyclicGame::rock_paper_scissors();
+                let letters = day02::puzzle_letters(&game);
+                let rounds =
+                    day02::parse_rounds(text.as_bytes(), &letters).map_err(|e| e.to_string())?;
+                let analysis = day02::analyze_guide(&game, &letters, &rounds);
+                (analysis.to_string(), analysis.to_json())
+            }
+        };
+
+        match format {
+            Format::Text => match input.name.as_str() {
+                DEFAULT_INPUT => print!("Day {day}\n{report}"),
+                name => print!("Day {day} [{name}]\n{report}"),
+            },
+            Format::Json => results.push(json!({
+                "day": day,
+                "input": input.name,
+                "stats": json,
+            })),
+        }
+    }
+
+    if format == Format::Json {
+        let report = json!({ "results": results });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("valid JSON")
+        );
+    }
+
+    Ok(true)
+}
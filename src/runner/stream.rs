@@ -0,0 +1,141 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    time::Instant,
+};
+
+use serde_json::json;
+
+use super::{Args, Format};
+use crate::{day01, error::ReadError};
+
+/// How many of the best elves are listed unless `--top` says otherwise.
+const DEFAULT_TOP: usize = 3;
+
+/// Day 1's answers and best elves, worked out without holding the input.
+#[derive(Debug, PartialEq, Eq)]
+struct Summary {
+    part1: i64,
+    part2: i64,
+    /// The index and total of the best elves, most first.
+    top: Vec<(usize, i64)>,
+}
+
+/// Reads day 1 from `reader` a line at a time, keeping only the `count` best elves.
+fn summarise(reader: impl BufRead, count: usize) -> Result<Summary, ReadError> {
+    // Part 2 needs the best three, however few are listed.
+    let mut top = day01::read_top_n_elves(reader, count.max(3))?;
+    let total = |n| top.iter().take(n).map(|&(_, total)| total).sum();
+    let (part1, part2) = (total(1), total(3));
+
+    top.truncate(count);
+
+    Ok(Summary { part1, part2, top })
+}
+
+/// Opens the file at `path`, or stdin if there is none or it is `-`.
+fn open(path: Option<&str>) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        None | Some("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+/// Solves a day from a file or stdin as it is read, so inputs too big to load can be piped in.
+///
+/// Only day 1 reads its input a line at a time so far.
+pub(super) fn stream(args: &Args) -> Result<bool, String> {
+    args.check_options(&["top", "format"])?;
+
+    let format = args.format()?;
+    let day = args.day(1)?.ok_or("missing day")?;
+
+    if day != 1 {
+        return Err(format!("day {day} can't be streamed"));
+    }
+
+    let count = match args.option("top") {
+        Some(n) => n.parse().map_err(|_| format!("invalid elf count `{n}`"))?,
+        None => DEFAULT_TOP,
+    };
+
+    let path = args.positional(2);
+    let reader = open(path).map_err(|e| format!("can't open {}: {e}", path.unwrap_or("-")))?;
+
+    let start = Instant::now();
+    let summary = summarise(reader, count).map_err(|e| e.to_string())?;
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("Day {day} part 1: {} ({elapsed:?})", summary.part1);
+            println!("Day {day} part 2: {}", summary.part2);
+
+            for (rank, (index, total)) in summary.top.iter().enumerate() {
+                println!("  #{:<3} elf {:<8} {total}", rank + 1, index + 1);
+            }
+        }
+        Format::Json => {
+            let top = summary
+                .top
+                .iter()
+                .map(|&(index, total)| json!({ "elf": index + 1, "calories": total }))
+                .collect::<Vec<_>>();
+
+            let report = json!({
+                "day": day,
+                "part1": summary.part1,
+                "part2": summary.part2,
+                "top": top,
+                "read_ns": elapsed.as_nanos() as u64,
+            });
+
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).expect("valid JSON")
+            );
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn summarises_streamed_elves() {
+        let input = "1000\n2000\n\n4000\n\n5000\n6000\n\n10000\n";
+
+        let summary = summarise(input.as_bytes(), 1).unwrap();
+        assert_eq!(
+            summary,
+            Summary {
+                part1: 11000,
+                part2: 25000,
+                top: vec![(2, 11000)],
+            }
+        );
+
+        let summary = summarise("7".as_bytes(), 5).unwrap();
+        assert_eq!((summary.part1, summary.part2), (7, 7));
+        assert_eq!(summary.top, [(0, 7)]);
+
+        assert!(summarise("1\n\nx".as_bytes(), 3).is_err());
+    }
+
+    #[test]
+    fn streams_files() {
+        let path = std::env::temp_dir().join(format!("aoc_2022_stream_{}.txt", std::process::id()));
+        fs::write(&path, "3\r\n4\r\n\r\n9\r\n").unwrap();
+
+        let summary = summarise(open(path.to_str()).unwrap(), 3);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(summary.unwrap().top, [(1, 9), (0, 7)]);
+        assert!(open(Some("no/such/file.txt")).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use super::{answers::Answers, run_solver, selected_inputs, Args};
+use crate::registry;
+
+/// How a solver's answer compared to the recorded one.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail(String),
+    Missing(&'static str),
+}
+
+fn check(answer: Result<String, String>, expected: Option<&str>) -> Verdict {
+    match (answer, expected) {
+        (Err(error), _) => Verdict::Fail(error),
+        (Ok(answer), Some(expected)) if answer == expected => Verdict::Pass,
+        (Ok(answer), Some(expected)) => {
+            Verdict::Fail(format!("expected {expected:?}, got {answer:?}"))
+        }
+        (Ok(_), None) => Verdict::Missing("no recorded answer"),
+    }
+}
+
+/// Runs every matching solver on each of its inputs, comparing against the recorded answers.
+///
+/// Succeeds unless some solver errors or gives an answer that differs from the recorded one.
+pub(super) fn verify(args: &Args) -> Result<bool, String> {
+    args.check_options(&["input"])?;
+
+    let answers = Answers::load()?;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    let solvers = registry::find(args.day(1)?, args.part(2)?, None).collect::<Vec<_>>();
+    let mut days = solvers.iter().map(|s| s.day).collect::<Vec<_>>();
+    days.dedup();
+
+    for day in days {
+        let inputs = selected_inputs(args, day)?;
+
+        for solver in solvers.iter().filter(|s| s.day == day) {
+            if inputs.is_empty() {
+                println!(
+                    "day{day} part{} {}: missing, no input",
+                    solver.part, solver.variant
+                );
+                missing += 1;
+            }
+
+            for input in &inputs {
+                let label = format!(
+                    "day{day} part{} {} [{}]",
+                    solver.part, solver.variant, input.name
+                );
+
+                let verdict = match input.load() {
+                    Ok(text) => check(
+                        run_solver(solver, &text).answer.map_err(|e| e.to_string()),
+                        answers.get(day, solver.part, &input.name),
+                    ),
+                    Err(e) => Verdict::Fail(format!("can't read {}: {e}", input.path.display())),
+                };
+
+                match verdict {
+                    Verdict::Pass => {
+                        println!("{label}: pass");
+                        passed += 1;
+                    }
+                    Verdict::Fail(reason) => {
+                        println!("{label}: FAIL, {reason}");
+                        failed += 1;
+                    }
+                    Verdict::Missing(reason) => {
+                        println!("{label}: missing, {reason}");
+                        missing += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {missing} missing");
+
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_answers() {
+        assert_eq!(check(Ok("12".into()), Some("12")), Verdict::Pass);
+        assert_eq!(
+            check(Ok("13".into()), Some("12")),
+            Verdict::Fail("expected \"12\", got \"13\"".into())
+        );
+        assert_eq!(
+            check(Err("bad input".into()), None),
+            Verdict::Fail("bad input".into())
+        );
+        assert!(matches!(check(Ok("12".into()), None), Verdict::Missing(_)));
+    }
+}
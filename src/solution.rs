@@ -0,0 +1,39 @@
+use crate::{error::ParseError, registry::IntoAnswer};
+
+/// A day's puzzle, split into a shared parse step and its two parts.
+///
+/// Every day implements this on a unit struct, e.g. `day01::Day01`, which the
+/// [registry](crate::registry) turns into its type-erased solvers.
+pub trait Solution {
+    const DAY: u8;
+
+    /// The puzzle's title, if it has been filled in.
+    const NAME: Option<&'static str> = None;
+
+    /// The parsed puzzle input both parts work on.
+    type Input: 'static;
+    type Output1: IntoAnswer;
+    type Output2: IntoAnswer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Output1;
+
+    fn part2(input: &Self::Input) -> Self::Output2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day01::Day01, day10::Day10};
+
+    #[test]
+    fn solves_with_typed_outputs() {
+        let elves = Day01::parse("1\n2\n\n4").unwrap();
+        assert_eq!(Day01::part1(&elves), 4);
+
+        let program = Day10::parse("noop").unwrap();
+        assert_eq!(Day10::part1(&program), 0);
+        assert_eq!(Day10::NAME, Some("Cathode-Ray Tube"));
+    }
+}